@@ -0,0 +1,1205 @@
+//! Derive macro behind [axum_error_macro](https://docs.rs/axum_error_macro).
+//! Depend on that crate instead of this one.
+
+use litrs::{IntegerLit, StringLit};
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Punct, Spacing, TokenTree};
+use quote::{quote, quote_spanned};
+use std::iter::Peekable;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::token::Comma;
+use syn::{Fields, MetaList, Variant};
+
+#[proc_macro_derive(ErrorResponse, attributes(error, error_format, sensitive))]
+pub fn axum_error_macro_derive(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    impl_error(ast)
+}
+
+fn impl_error(ast: syn::DeriveInput) -> TokenStream {
+    let ident = &ast.ident;
+    let eident = Ident::new(&format!("Derive{}", ident), ident.span());
+
+    let ErrorFormat {
+        content_type,
+        template,
+        root,
+        with,
+        pinned,
+    } = retrieve_format(&ast);
+    let fallback = if pinned {
+        quote!(#content_type)
+    } else {
+        quote!(axum_error_macro::__private::configured_format())
+    };
+    let template = quote_option(&template);
+    let mut binary = proc_macro2::TokenStream::new();
+    if cfg!(feature = "msgpack") {
+        binary.extend(quote!(
+            "application/msgpack" => axum_error_macro::formats::MsgPack.format(context),
+        ));
+    }
+    if cfg!(feature = "prost") {
+        binary.extend(quote!(
+            "application/x-protobuf" => axum_error_macro::formats::Protobuf.format(context),
+        ));
+    }
+    if cfg!(feature = "cbor") {
+        binary.extend(quote!(
+            "application/cbor" => axum_error_macro::formats::Cbor.format(context),
+        ));
+    }
+    if let Some(with) = with {
+        binary.extend(quote!(#CUSTOM => (#with).format(context),));
+    }
+    let EnumOptions {
+        cache,
+        metric,
+        request_id,
+        attach,
+    } = retrieve_options(&ast);
+    let request_id = request_id.map(|header| {
+        quote!(if let Some(id) = axum_error_macro::__private::current_request_id() {
+            axum_error_macro::__private::insert_header(
+                &mut self.headers,
+                axum::http::HeaderName::from_static(#header),
+                &id,
+            );
+            self.context.request_id = Some(id);
+        })
+    });
+    let mut errors = retrieve_errors(retrieve_variants(&ast));
+    for error in &mut errors {
+        let cache = error
+            .attribute
+            .cache
+            .take()
+            .unwrap_or_else(|| cache.clone());
+        error
+            .attribute
+            .headers
+            .insert(0, ("cache-control".into(), cache));
+    }
+    let mut warnings = proc_macro2::TokenStream::new();
+    for error in &errors {
+        let format = error.attribute.format.as_ref().unwrap_or(&content_type);
+        if format == "oauth2" && error.attribute.oauth_error.is_none() {
+            panic!("Expected 'oauth_error' on {} for \"oauth2\"", error.ident);
+        }
+        let missing = match error.attribute.code {
+            401 if format != "oauth2" && !error.attribute.has_header("www-authenticate") => {
+                Some(("auth_challenge", "WWW-Authenticate"))
+            }
+            405 if !error.attribute.has_header("allow") => Some(("allow", "Allow")),
+            _ => None,
+        };
+        if let Some((option, header)) = missing {
+            warnings.extend(missing_header_warning(&error.ident, option, header));
+        }
+    }
+    let matches = match_error(ident, &eident, &errors);
+    let backtraces: Vec<_> = errors
+        .iter()
+        .filter_map(|error| backtrace_arm(ident, error))
+        .collect();
+    let error = if backtraces.is_empty() {
+        quote!(let error = #eident::from(&self);)
+    } else {
+        let others = (backtraces.len() < errors.len()).then(|| quote!(_ => None,));
+        quote!(
+            let mut error = #eident::from(&self);
+            error.context.backtrace = match &self {
+                #(#backtraces)*
+                #others
+            };
+        )
+    };
+    let into_response = if attach {
+        quote!(
+            #error
+            let mut response = error.into_response();
+            response.extensions_mut().insert(std::sync::Arc::new(self));
+            response
+        )
+    } else {
+        quote!(
+            #error
+            error.into_response()
+        )
+    };
+    let internals: Vec<_> = errors
+        .iter()
+        .filter_map(|error| internal_arm(ident, error))
+        .collect();
+    let internal_message = if internals.is_empty() {
+        quote!(None)
+    } else {
+        let others = (internals.len() < errors.len()).then(|| quote!(_ => None,));
+        quote!(match self {
+            #(#internals)*
+            #others
+        })
+    };
+    let constructors = errors.iter().filter_map(backtrace_constructor);
+    let tonic = if cfg!(feature = "tonic") {
+        quote!(
+            impl From<#ident> for axum_error_macro::__private::tonic::Status {
+                fn from(error: #ident) -> Self {
+                    let error = #eident::from(&error);
+                    axum_error_macro::__private::tonic::Status::new(
+                        error.context.grpc_code().into(),
+                        error.context.message,
+                    )
+                }
+            }
+        )
+    } else {
+        proc_macro2::TokenStream::new()
+    };
+    let graphql = if cfg!(feature = "async-graphql") {
+        quote!(
+            impl axum_error_macro::__private::async_graphql::ErrorExtensions for #ident {
+                fn extend(&self) -> axum_error_macro::__private::async_graphql::Error {
+                    let error = #eident::from(self);
+                    let error = error.context;
+                    axum_error_macro::__private::async_graphql::Error::new(error.message).extend_with(
+                        |_, extensions| {
+                            extensions.set("status", error.status.as_u16());
+                            extensions.set("variant", error.variant);
+                            if let Some(error_code) = error.error_code {
+                                extensions.set("code", error_code);
+                            }
+                        },
+                    )
+                }
+            }
+
+            impl From<#ident> for axum_error_macro::__private::async_graphql::Error {
+                fn from(error: #ident) -> Self {
+                    axum_error_macro::__private::async_graphql::ErrorExtensions::extend(&error)
+                }
+            }
+        )
+    } else {
+        proc_macro2::TokenStream::new()
+    };
+
+    let expanded = quote!(
+          use axum::response::IntoResponse;
+
+          struct #eident {
+            context: axum_error_macro::ErrorContext,
+            headers: axum::http::HeaderMap,
+            format: Option<&'static str>,
+            log: Option<&'static str>,
+          }
+
+          impl #eident {
+            pub fn status(code: u16) -> axum::http::StatusCode {
+               axum::http::StatusCode::from_u16(code).expect("Error code must be valid")
+            }
+
+            pub fn generate_response(&self, format: &str) -> axum_error_macro::ErrorBody {
+                use axum_error_macro::ErrorFormatter;
+
+                let context = &self.context;
+                match format {
+                    "application/json" => axum_error_macro::formats::Json.format(context),
+                    "text/plain" => axum_error_macro::formats::PlainText.format(context),
+                    "text/html" => axum_error_macro::formats::Html { template: #template }.format(context),
+                    "application/xml" => axum_error_macro::formats::Xml { root: #root }.format(context),
+                    "application/vnd.api+json" => axum_error_macro::formats::JsonApi.format(context),
+                    "application/problem+json" => axum_error_macro::formats::ProblemJson.format(context),
+                    "json-rpc" => axum_error_macro::formats::JsonRpc.format(context),
+                    "oauth2" => axum_error_macro::formats::OAuth2.format(context),
+                    "connect" => axum_error_macro::formats::Connect.format(context),
+                    #binary
+                    _ => panic!("Wrong format type")
+                }
+            }
+          }
+
+          impl axum::response::IntoResponse for #eident {
+            fn into_response(mut self) -> axum::response::Response {
+                #request_id
+                axum_error_macro::__private::trace_error(&self.context, self.log);
+                axum_error_macro::__private::count_error(#metric, stringify!(#ident), &self.context);
+                axum_error_macro::__private::mark_span(stringify!(#ident), &self.context);
+                axum_error_macro::__private::log_backtrace(&self.context);
+                axum_error_macro::__private::apply_config(&mut self.context);
+                let fallback = #fallback;
+                let format = match self.format {
+                    Some(format) => format,
+                    None if axum_error_macro::__private::FORMATS.contains(&fallback) => {
+                        axum_error_macro::__private::negotiated_format(
+                            axum_error_macro::__private::FORMATS,
+                            fallback,
+                        )
+                    }
+                    None => fallback,
+                };
+                let body = self.generate_response(format);
+                let record = axum_error_macro::ErrorRecord {
+                    error: stringify!(#ident),
+                    variant: self.context.variant,
+                    error_code: self.context.error_code,
+                    status: self.context.status,
+                };
+                let mut response =
+                    axum_error_macro::__private::respond(self.context.status, self.headers, body);
+                response.extensions_mut().insert(record);
+                response
+            }
+          }
+
+          impl From<&#ident> for #eident {
+            fn from(error: &#ident) -> Self {
+                match error {
+                  #(#matches),*
+                }
+            }
+          }
+
+            impl #ident {
+                #(#constructors)*
+
+                /// `internal = "..."` message of the variant, meant for logs.
+                pub fn internal_message(&self) -> Option<String> {
+                    #internal_message
+                }
+            }
+
+            impl axum::response::IntoResponse for #ident {
+                fn into_response(self) -> axum::response::Response {
+                    #into_response
+                }
+            }
+
+          #tonic
+
+          #graphql
+
+          #warnings
+    );
+
+    expanded.into()
+}
+
+/// Formats rendered as "application/json" bodies of a specific protocol, never negotiated.
+const PROTOCOLS: &[&str] = &["json-rpc", "oauth2", "connect"];
+
+/// Format of enums rendered by their own `with = ...` formatter, never negotiated.
+const CUSTOM: &str = "custom";
+
+struct ErrorFormat {
+    content_type: String,
+    template: Option<proc_macro2::TokenStream>,
+    root: String,
+    with: Option<proc_macro2::TokenStream>,
+    pinned: bool,
+}
+
+fn retrieve_format(ast: &syn::DeriveInput) -> ErrorFormat {
+    let mut format = ErrorFormat {
+        content_type: "text/plain".into(),
+        template: None,
+        root: "error".into(),
+        with: None,
+        pinned: false,
+    };
+    let attr = ast
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("error_format"));
+    if let Some(syn::Meta::List(meta_list)) = attr.map(|attr| &attr.meta) {
+        format.pinned = true;
+        let mut tokens = meta_list.tokens.clone().into_iter().peekable();
+        let options = match tokens.peek() {
+            Some(TokenTree::Ident(i)) if i == "with" => {
+                format.content_type = CUSTOM.into();
+                let mut options: proc_macro2::TokenStream =
+                    TokenTree::Punct(Punct::new(',', Spacing::Alone)).into();
+                options.extend(tokens);
+                parse_options(options.into_iter().peekable())
+            }
+            _ => {
+                format.content_type = match tokens.next() {
+                    Some(TokenTree::Literal(ref i)) => match StringLit::try_from(i) {
+                        Err(e) => panic!("{}", e.to_compile_error()),
+                        Ok(lit) => lit.value().to_string(),
+                    },
+                    tt => panic!("Expected format type, found {:?}", tt),
+                };
+                validate_format(&format.content_type);
+                parse_options(tokens)
+            }
+        };
+
+        for (option, value) in options {
+            match option.to_string().as_str() {
+                "with" if format.content_type == CUSTOM && format.with.is_none() => {
+                    format.with = Some(value)
+                }
+                "with" => panic!("'with' replaces the format type and is only allowed once"),
+                "template" if format.content_type == "text/html" => format.template = Some(value),
+                "template" => panic!("Templates are only available for \"text/html\""),
+                "root" if format.content_type == "application/xml" => {
+                    format.root = parse_string(value);
+                    if !is_xml_name(&format.root) {
+                        panic!("'{}' is not a valid XML element name", format.root);
+                    }
+                }
+                "root" => panic!("Root element is only available for \"application/xml\""),
+                _ => panic!("Unknown format option '{}'", option),
+            }
+        }
+    }
+    format
+}
+
+struct EnumOptions {
+    cache: String,
+    metric: String,
+    request_id: Option<String>,
+    attach: bool,
+}
+
+/// Options of `#[error(...)]` on the enum itself.
+fn retrieve_options(ast: &syn::DeriveInput) -> EnumOptions {
+    let mut options = EnumOptions {
+        cache: "no-store".into(),
+        metric: "http_errors_total".into(),
+        request_id: None,
+        attach: false,
+    };
+    let attr = ast.attrs.iter().find(|attr| attr.path().is_ident("error"));
+    if let Some(attr) = attr {
+        let syn::Meta::List(meta_list) = &attr.meta else {
+            panic!("Expected #[error(option = \"...\")]");
+        };
+        let mut tokens: proc_macro2::TokenStream =
+            TokenTree::Punct(Punct::new(',', Spacing::Alone)).into();
+        tokens.extend(meta_list.tokens.clone());
+        for (option, value) in parse_options(tokens.into_iter().peekable()) {
+            match option.to_string().as_str() {
+                "cache" => {
+                    options.cache = parse_string(value);
+                    validate_header_value(&options.cache);
+                }
+                "metric" => {
+                    options.metric = parse_string(value);
+                    if options.metric.is_empty() {
+                        panic!("Expected a metric name");
+                    }
+                }
+                "request_id" => {
+                    let mut tokens = value.into_iter();
+                    options.request_id = Some(header_name(tokens.next()));
+                    if let Some(tt) = tokens.next() {
+                        panic!("Expected a single header name, found {}", tt);
+                    }
+                }
+                "attach" => {
+                    if !value.is_empty() {
+                        panic!("Expected #[error(attach)], found '{}'", value);
+                    }
+                    options.attach = true;
+                }
+                _ => panic!("Unknown enum option '{}'", option),
+            }
+        }
+    }
+    options
+}
+
+fn validate_format(format: &str) {
+    match format {
+        "application/json"
+        | "text/plain"
+        | "text/html"
+        | "application/xml"
+        | "application/vnd.api+json"
+        | "application/problem+json" => {}
+        format if PROTOCOLS.contains(&format) => {}
+        "application/msgpack" if cfg!(feature = "msgpack") => {}
+        "application/cbor" if cfg!(feature = "cbor") => {}
+        "application/x-protobuf" if cfg!(feature = "prost") => {}
+        "application/msgpack" => panic!("\"application/msgpack\" requires the \"msgpack\" feature"),
+        "application/cbor" => panic!("\"application/cbor\" requires the \"cbor\" feature"),
+        "application/x-protobuf" => {
+            panic!("\"application/x-protobuf\" requires the \"prost\" feature")
+        }
+        _ => panic!("Wrong format type"),
+    }
+}
+
+fn parse_options(
+    mut tokens: Peekable<proc_macro2::token_stream::IntoIter>,
+) -> Vec<(Ident, proc_macro2::TokenStream)> {
+    let mut options = Vec::new();
+    while let Some(tt) = tokens.next() {
+        match tt {
+            TokenTree::Punct(ref i) => assert_eq!(i.as_char(), ','),
+            tt => panic!("Expected ',' , found {}", tt),
+        };
+        let option = match tokens.next() {
+            Some(TokenTree::Ident(i)) => i,
+            tt => panic!("Expected option, found {:?}", tt),
+        };
+        match tokens.peek() {
+            Some(TokenTree::Punct(i)) if i.as_char() == '=' => {
+                tokens.next();
+            }
+            Some(TokenTree::Group(_)) => {}
+            Some(tt) if is_comma(tt) => {}
+            None => {}
+            tt => panic!("Expected '=' , found {:?}", tt),
+        };
+        let mut value = proc_macro2::TokenStream::new();
+        while let Some(tt) = tokens.next_if(|tt| !is_comma(tt)) {
+            value.extend([tt]);
+        }
+        options.push((option, value));
+    }
+    options
+}
+
+fn parse_string(value: proc_macro2::TokenStream) -> String {
+    let mut tokens = value.into_iter();
+    match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(ref i)), None) => match StringLit::try_from(i) {
+            Err(e) => panic!("{}", e.to_compile_error()),
+            Ok(lit) => lit.value().to_string(),
+        },
+        (tt, _) => panic!("Expected string literal, found {:?}", tt),
+    }
+}
+
+fn parse_int(value: proc_macro2::TokenStream) -> i64 {
+    let mut tokens = value.into_iter().peekable();
+    let negative = tokens
+        .next_if(|tt| matches!(tt, TokenTree::Punct(i) if i.as_char() == '-'))
+        .is_some();
+    let value: i64 = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(ref i)), None) => match IntegerLit::try_from(i) {
+            Err(e) => panic!("{}", e.to_compile_error()),
+            Ok(lit) => lit.value().expect("Integer is out of range"),
+        },
+        (tt, _) => panic!("Expected integer literal, found {:?}", tt),
+    };
+    if negative {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Characters allowed in OAuth 2.0 `error` and `error_uri` values (RFC 6749, appendix A).
+fn is_oauth_char(c: char) -> bool {
+    matches!(c, '\x20'..='\x21' | '\x23'..='\x5B' | '\x5D'..='\x7E')
+}
+
+fn is_xml_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+fn is_comma(tt: &TokenTree) -> bool {
+    matches!(tt, TokenTree::Punct(i) if i.as_char() == ',')
+}
+
+fn retrieve_variants(ast: &syn::DeriveInput) -> Punctuated<Variant, Comma> {
+    if let syn::Data::Enum(syn::DataEnum { variants, .. }) = &ast.data {
+        variants.to_owned()
+    } else {
+        panic!("Expected at least one enum variant")
+    }
+}
+
+struct Params {
+    bindings: Vec<proc_macro2::TokenStream>,
+    format: Vec<proc_macro2::TokenStream>,
+    details: Option<proc_macro2::TokenStream>,
+    pointer: Option<proc_macro2::TokenStream>,
+    sensitive: Vec<proc_macro2::TokenStream>,
+    backtrace: Option<proc_macro2::TokenStream>,
+    types: Vec<syn::Type>,
+}
+
+fn retrieve_params(fields: Fields, headers: &[(String, usize)]) -> Option<Params> {
+    if let syn::Fields::Unnamed(pfields) = fields {
+        if pfields.unnamed.is_empty() {
+            None
+        } else {
+            let mut params = Params {
+                bindings: Vec::new(),
+                format: Vec::new(),
+                details: None,
+                pointer: None,
+                sensitive: Vec::new(),
+                backtrace: None,
+                types: Vec::new(),
+            };
+            for (index, field) in pfields.unnamed.into_iter().enumerate() {
+                let unique_param_ident = Ident::new(
+                    &format!("param_{}", char::from_u32(index as u32 + 97).unwrap()),
+                    field.span(),
+                );
+                params.bindings.push(quote!(#unique_param_ident));
+                params.types.push(field.ty.clone());
+                let marker = field.attrs.iter().find_map(field_marker);
+                let header = headers.iter().any(|(_, field)| *field == index);
+                if field
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident("sensitive"))
+                {
+                    if marker.is_some() || header {
+                        panic!("Sensitive fields can only be used in messages");
+                    }
+                    params.sensitive.push(quote!(#unique_param_ident));
+                }
+                match marker.as_deref() {
+                    Some("details") if params.details.is_some() => {
+                        panic!("Expected at most one details field")
+                    }
+                    Some("details") => params.details = Some(quote!(#unique_param_ident)),
+                    Some("pointer") if params.pointer.is_some() => {
+                        panic!("Expected at most one pointer field")
+                    }
+                    Some("pointer") => params.pointer = Some(quote!(#unique_param_ident)),
+                    Some("backtrace") if params.backtrace.is_some() => {
+                        panic!("Expected at most one backtrace field")
+                    }
+                    Some("backtrace") => params.backtrace = Some(quote!(#unique_param_ident)),
+                    _ if header => {}
+                    _ => params.format.push(quote!(#unique_param_ident)),
+                }
+            }
+            Some(params)
+        }
+    } else {
+        None
+    }
+}
+
+fn field_marker(attr: &syn::Attribute) -> Option<String> {
+    if !attr.path().is_ident("error") {
+        return None;
+    }
+    match &attr.meta {
+        syn::Meta::List(meta_list) => match meta_list.tokens.clone().into_iter().next() {
+            Some(TokenTree::Ident(ref i))
+                if i == "details" || i == "pointer" || i == "backtrace" =>
+            {
+                Some(i.to_string())
+            }
+            tt => panic!(
+                "Expected 'details', 'pointer' or 'backtrace', found {:?}",
+                tt
+            ),
+        },
+        _ => panic!("Expected #[error(details)], #[error(pointer)] or #[error(backtrace)]"),
+    }
+}
+
+struct ErrorVariant {
+    ident: Ident,
+    attribute: ErrorAttribute,
+    params: Option<Params>,
+}
+
+fn retrieve_errors(variants: Punctuated<Variant, Comma>) -> Vec<ErrorVariant> {
+    variants
+        .into_iter()
+        .filter_map(|v| {
+            if let syn::Meta::List(meta_list) = &v.attrs[0].meta {
+                let attribute = validate_attribute(meta_list);
+                for (name, field) in &attribute.field_headers {
+                    if *field >= v.fields.len() {
+                        panic!("Header '{}' refers to a missing field {}", name, field);
+                    }
+                }
+                Some(ErrorVariant {
+                    params: retrieve_params(v.fields.clone(), &attribute.field_headers),
+                    attribute,
+                    ident: v.ident,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn match_error(
+    ident: &Ident,
+    eident: &Ident,
+    errors: &[ErrorVariant],
+) -> Vec<proc_macro2::TokenStream> {
+    errors
+        .iter()
+        .map(|error| {
+            let bident = &error.ident;
+            let ErrorAttribute {
+                code,
+                msg,
+                title,
+                rpc_code,
+                oauth_error,
+                error_uri,
+                grpc,
+                format,
+                headers,
+                field_headers,
+                log,
+                error_code,
+                ..
+            } = &error.attribute;
+
+            let (match_params, details, pointer) = if let Some(params) = &error.params {
+                let Params {
+                    bindings,
+                    details,
+                    pointer,
+                    ..
+                } = params;
+                let details = details.as_ref().map(
+                    |param| quote!(axum_error_macro::__private::serde_json::to_value(#param).ok()),
+                );
+                let pointer = pointer
+                    .as_ref()
+                    .map(|param| quote!(Some(#param.to_string())));
+                (Some(quote!((#(#bindings),*))), details, pointer)
+            } else {
+                (None, None, None)
+            };
+            let details = details.unwrap_or(quote!(None));
+            let pointer = pointer.unwrap_or(quote!(None));
+            let title = quote_option(title);
+            let rpc_code = quote_option(rpc_code);
+            let oauth_error = quote_option(oauth_error);
+            let error_uri = quote_option(error_uri);
+            let grpc = quote_option(grpc);
+            let format = quote_option(format);
+            let log = quote_option(log);
+            let error_code = quote_option(error_code);
+            let format_fields = match &error.params {
+                Some(params) => {
+                    let format_fields = params.format.iter().map(|param| {
+                        let param = param.to_string();
+                        let index = params
+                            .bindings
+                            .iter()
+                            .position(|field| field.to_string() == param)
+                            .expect("Formatted fields are bound");
+                        let sensitive = params
+                            .sensitive
+                            .iter()
+                            .any(|field| field.to_string() == param);
+                        (index, (!sensitive).then_some(param))
+                    });
+                    format_fields.collect()
+                }
+                None => Vec::new(),
+            };
+            let count = error
+                .params
+                .as_ref()
+                .map_or(0, |params| params.bindings.len());
+            let msg = capture_fields("msg", msg, &format_fields, count);
+            let internal = match internal_template(error) {
+                Some(internal) => quote!(Some(format!(#internal))),
+                None => quote!(None),
+            };
+            let headers = headers.iter().map(|(name, value)| {
+                quote!(headers.insert(
+                    axum::http::HeaderName::from_static(#name),
+                    axum::http::HeaderValue::from_static(#value),
+                );)
+            });
+            let field_headers = field_headers.iter().map(|(name, field)| {
+                let param = &error.params.as_ref().unwrap().bindings[*field];
+                quote!(axum_error_macro::__private::insert_header(
+                    &mut headers,
+                    axum::http::HeaderName::from_static(#name),
+                    #param,
+                );)
+            });
+
+            let bindings = error
+                .params
+                .as_ref()
+                .map(|params| &params.bindings)
+                .into_iter()
+                .flatten();
+
+            quote!(
+              #ident::#bident #match_params => {
+                #(let _ = &#bindings;)*
+                #[allow(unused_mut)]
+                let mut headers = axum::http::HeaderMap::new();
+                #(#headers)*
+                #(#field_headers)*
+                #eident {
+                context: {
+                  let mut context = axum_error_macro::ErrorContext::new(
+                    #eident::status(#code),
+                    stringify!(#bident),
+                    format!(#msg),
+                  );
+                  context.details = #details;
+                  context.title = #title;
+                  context.pointer = #pointer;
+                  context.rpc_code = #rpc_code;
+                  context.oauth_error = #oauth_error;
+                  context.error_uri = #error_uri;
+                  context.grpc = #grpc;
+                  context.internal = #internal;
+                  context.error_code = #error_code;
+                  context
+                },
+                headers,
+                format: #format,
+                log: #log,
+              }
+              }
+            )
+        })
+        .collect()
+}
+
+/// `internal = "..."` of the variant with its fields captured.
+fn internal_template(error: &ErrorVariant) -> Option<String> {
+    let internal = error.attribute.internal.as_ref()?;
+    let fields: Vec<_> = error
+        .params
+        .iter()
+        .flat_map(|params| &params.bindings)
+        .enumerate()
+        .map(|(index, param)| (index, Some(param.to_string())))
+        .collect();
+    Some(capture_fields("internal", internal, &fields, fields.len()))
+}
+
+/// Match arm of `internal_message()` for a variant with `internal = "..."`.
+fn internal_arm(ident: &Ident, error: &ErrorVariant) -> Option<proc_macro2::TokenStream> {
+    let internal = internal_template(error)?;
+    let bident = &error.ident;
+    let bindings = error.params.as_ref().map(|params| &params.bindings);
+    let match_params = bindings.map(|bindings| quote!((#(#bindings),*)));
+    let bindings = bindings.into_iter().flatten();
+    Some(quote!(
+        #ident::#bident #match_params => {
+            #(let _ = &#bindings;)*
+            Some(format!(#internal))
+        }
+    ))
+}
+
+/// Match arm rendering the #[error(backtrace)] field of a variant, only done when it becomes a
+/// response since it symbolizes the backtrace.
+fn backtrace_arm(ident: &Ident, error: &ErrorVariant) -> Option<proc_macro2::TokenStream> {
+    let params = error.params.as_ref()?;
+    let backtrace = params.backtrace.as_ref()?;
+    let bident = &error.ident;
+    let patterns = params.bindings.iter().map(|param| {
+        if param.to_string() == backtrace.to_string() {
+            quote!(#param)
+        } else {
+            quote!(_)
+        }
+    });
+    Some(quote!(
+        #ident::#bident(#(#patterns),*) => axum_error_macro::__private::captured_backtrace(#backtrace),
+    ))
+}
+
+/// `snake_case` constructor of a variant with a #[error(backtrace)] field, capturing it.
+fn backtrace_constructor(error: &ErrorVariant) -> Option<proc_macro2::TokenStream> {
+    let params = error.params.as_ref()?;
+    let backtrace = params.backtrace.as_ref()?.to_string();
+    let variant = &error.ident;
+    let mut name = String::new();
+    for (index, c) in variant.to_string().chars().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            name.push('_');
+        }
+        name.push(c.to_ascii_lowercase());
+    }
+    let name = Ident::new(&name, variant.span());
+    let mut arguments = Vec::new();
+    let mut values = Vec::new();
+    for (param, ty) in params.bindings.iter().zip(&params.types) {
+        if param.to_string() == backtrace {
+            values.push(quote!(std::backtrace::Backtrace::capture()));
+        } else {
+            arguments.push(quote!(#param: #ty));
+            values.push(quote!(#param));
+        }
+    }
+    let doc = format!(
+        "Creates [`Self::{}`], capturing where it was created.",
+        variant
+    );
+    Some(quote!(
+        #[doc = #doc]
+        pub fn #name(#(#arguments),*) -> Self {
+            Self::#variant(#(#values),*)
+        }
+    ))
+}
+
+/// Emits a deprecation warning on `variant`, as proc macros can not emit warnings on stable.
+fn missing_header_warning(variant: &Ident, option: &str, header: &str) -> proc_macro2::TokenStream {
+    let note = format!(
+        "{} has no `{}`, but HTTP requires the `{}` header on its status",
+        variant, option, header
+    );
+    let marker = Ident::new(&format!("{}_without_{}", variant, option), variant.span());
+    quote_spanned!(variant.span()=>
+        const _: () = {
+            #[deprecated(note = #note)]
+            #[allow(non_camel_case_types)]
+            struct #marker;
+            let _ = #marker;
+        };
+    )
+}
+
+/// Rewrites `{0}` and `{}` in the `option` template to captures of `fields`, so fields it
+/// does not mention are not reported as unused arguments. `None` fields are masked as `***`.
+///
+/// `{}` takes the next of `fields`, while `{0}` names the variant's field 0 so it means the same
+/// in `msg` and `internal`. `count` is the number of the variant's fields.
+fn capture_fields(
+    option: &str,
+    template: &str,
+    fields: &[(usize, Option<String>)],
+    count: usize,
+) -> String {
+    let mut captured = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+    let mut next = 0;
+    while let Some(c) = chars.next() {
+        if let Some(escaped) = chars.next_if(|next| matches!((c, next), ('{', '{') | ('}', '}'))) {
+            captured.push(c);
+            captured.push(escaped);
+        } else if c == '{' {
+            let mut argument = String::new();
+            while let Some(c) = chars.next_if(|c| *c != '}' && *c != ':') {
+                argument.push(c);
+            }
+            let field = match argument.trim() {
+                "" => {
+                    next += 1;
+                    match fields.get(next - 1) {
+                        Some((_, field)) => field,
+                        None => panic!("'{}' refers to a missing field {}", option, next - 1),
+                    }
+                }
+                argument => match argument.parse::<usize>() {
+                    Ok(index) => match fields.iter().find(|(field, _)| *field == index) {
+                        Some((_, field)) => field,
+                        None if index < count => panic!(
+                            "'{}' can not refer to field {} because it is not formatted",
+                            option, index
+                        ),
+                        None => panic!("'{}' refers to a missing field {}", option, index),
+                    },
+                    Err(_) => panic!(
+                        "Expected a field index in '{}', found '{}'",
+                        option, argument
+                    ),
+                },
+            };
+            match field {
+                Some(field) => {
+                    captured.push('{');
+                    captured.push_str(field);
+                }
+                None => {
+                    captured.push_str("***");
+                    while chars.next_if(|c| *c != '}').is_some() {}
+                    chars.next();
+                }
+            }
+        } else {
+            captured.push(c);
+        }
+    }
+    captured
+}
+
+fn quote_option<T: quote::ToTokens>(value: &Option<T>) -> proc_macro2::TokenStream {
+    match value {
+        Some(value) => quote!(Some(#value)),
+        None => quote!(None),
+    }
+}
+
+struct ErrorAttribute {
+    code: u16,
+    msg: String,
+    grpc: Option<i32>,
+    title: Option<String>,
+    rpc_code: Option<i64>,
+    oauth_error: Option<String>,
+    error_uri: Option<String>,
+    format: Option<String>,
+    headers: Vec<(String, String)>,
+    field_headers: Vec<(String, usize)>,
+    cache: Option<String>,
+    internal: Option<String>,
+    log: Option<String>,
+    error_code: Option<String>,
+}
+
+impl ErrorAttribute {
+    fn has_header(&self, name: &str) -> bool {
+        self.headers.iter().any(|(header, _)| header == name)
+            || self.field_headers.iter().any(|(header, _)| header == name)
+    }
+}
+
+fn validate_attribute(meta_list: &MetaList) -> ErrorAttribute {
+    let mut tokens = meta_list.tokens.clone().into_iter().peekable();
+    match tokens.next().unwrap() {
+        TokenTree::Ident(ref i) => assert_eq!(i, "code"),
+        tt => panic!("Expected 'code', found {}", tt),
+    };
+    match tokens.next().unwrap() {
+        TokenTree::Punct(ref i) => assert_eq!(i.as_char(), '='),
+        tt => panic!("Expected '=' , found {}", tt),
+    };
+    let error_code: u16 = match tokens.next().unwrap() {
+        TokenTree::Literal(ref i) => {
+            let int_lit = match IntegerLit::try_from(i) {
+                Err(e) => panic!("{}", e.to_compile_error()),
+                Ok(lit) => lit,
+            };
+            int_lit.value().unwrap()
+        }
+        tt => panic!("Expected http code error, found {}", tt),
+    };
+    match tokens.next().unwrap() {
+        TokenTree::Punct(ref i) => assert_eq!(i.as_char(), ','),
+        tt => panic!("Expected ',' , found {}", tt),
+    };
+    match tokens.next().unwrap() {
+        TokenTree::Ident(ref i) => assert_eq!(i, "msg"),
+        tt => panic!("Expected 'msg', found {}", tt),
+    };
+    match tokens.next().unwrap() {
+        TokenTree::Punct(ref i) => assert_eq!(i.as_char(), '='),
+        tt => panic!("Expected '=' , found {}", tt),
+    };
+    let error_msg = match tokens.next().unwrap() {
+        TokenTree::Literal(ref i) => {
+            let string_lit = match StringLit::try_from(i) {
+                Err(e) => panic!("{}", e.to_compile_error()),
+                Ok(lit) => lit,
+            };
+            string_lit.value().to_string()
+        }
+        tt => panic!("Expected error message, found {}", tt),
+    };
+
+    let mut attribute = ErrorAttribute {
+        code: error_code,
+        msg: error_msg,
+        grpc: None,
+        title: None,
+        rpc_code: None,
+        oauth_error: None,
+        error_uri: None,
+        format: None,
+        headers: Vec::new(),
+        field_headers: Vec::new(),
+        cache: None,
+        internal: None,
+        log: None,
+        error_code: None,
+    };
+    for (option, value) in parse_options(tokens) {
+        match option.to_string().as_str() {
+            "grpc" => {
+                let name = parse_string(value);
+                match GRPC_CODES.iter().position(|code| *code == name) {
+                    Some(code) => attribute.grpc = Some(code as i32),
+                    None => panic!("'{}' is not a gRPC status code", name),
+                }
+            }
+            "format" => {
+                let format = parse_string(value);
+                validate_format(&format);
+                attribute.format = Some(format);
+            }
+            "title" => attribute.title = Some(parse_string(value)),
+            "rpc_code" => attribute.rpc_code = Some(parse_int(value)),
+            "oauth_error" => {
+                let oauth_error = parse_string(value);
+                if oauth_error.is_empty() || !oauth_error.chars().all(is_oauth_char) {
+                    panic!("'{}' is not a valid OAuth 2.0 error code", oauth_error);
+                }
+                attribute.oauth_error = Some(oauth_error);
+            }
+            "error_uri" => {
+                let error_uri = parse_string(value);
+                if !error_uri.chars().all(is_oauth_char) {
+                    panic!("'{}' is not a valid OAuth 2.0 error URI", error_uri);
+                }
+                attribute.error_uri = Some(error_uri);
+            }
+            "auth_challenge" => {
+                let challenge = parse_string(value);
+                validate_header_value(&challenge);
+                attribute
+                    .headers
+                    .push(("www-authenticate".into(), challenge));
+            }
+            "allow" => match value.clone().into_iter().next() {
+                Some(TokenTree::Literal(ref i)) if StringLit::try_from(i).is_ok() => {
+                    let methods = parse_string(value);
+                    validate_header_value(&methods);
+                    attribute.headers.push(("allow".into(), methods));
+                }
+                _ => match usize::try_from(parse_int(value)) {
+                    Ok(field) => attribute.field_headers.push(("allow".into(), field)),
+                    Err(_) => panic!("Expected allowed methods or a field index"),
+                },
+            },
+            "internal" => attribute.internal = Some(parse_string(value)),
+            "error_code" => {
+                let error_code = parse_string(value);
+                if error_code.is_empty() {
+                    panic!("Expected an error code");
+                }
+                attribute.error_code = Some(error_code);
+            }
+            "log" => {
+                let level = parse_string(value);
+                if !["trace", "debug", "info", "warn", "error"].contains(&level.as_str()) {
+                    panic!("'{}' is not a log level", level);
+                }
+                attribute.log = Some(level);
+            }
+            "cache" => {
+                let cache = parse_string(value);
+                validate_header_value(&cache);
+                attribute.cache = Some(cache);
+            }
+            "headers" => attribute.headers.extend(parse_headers(value)),
+            "header" => attribute.field_headers.push(parse_field_header(value)),
+            _ => panic!("Unknown error option '{}'", option),
+        }
+    }
+    attribute
+}
+
+/// Parses `("Retry-After" = "30", ...)` into lowercase header names and their values.
+fn parse_headers(value: proc_macro2::TokenStream) -> Vec<(String, String)> {
+    let mut tokens = value.into_iter();
+    let group = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Group(group)), None) => group,
+        (tt, _) => panic!(
+            "Expected headers(\"Name\" = \"value\", ...), found {:?}",
+            tt
+        ),
+    };
+    let mut headers = Vec::new();
+    let mut tokens = group.stream().into_iter().peekable();
+    while tokens.peek().is_some() {
+        let name = header_name(tokens.next());
+        match tokens.next() {
+            Some(TokenTree::Punct(ref i)) if i.as_char() == '=' => {}
+            tt => panic!("Expected '=' , found {:?}", tt),
+        };
+        let value = match tokens.next() {
+            Some(TokenTree::Literal(ref i)) => match StringLit::try_from(i) {
+                Err(e) => panic!("{}", e.to_compile_error()),
+                Ok(lit) => lit.value().to_string(),
+            },
+            tt => panic!("Expected header value, found {:?}", tt),
+        };
+        validate_header_value(&value);
+        headers.push((name, value));
+        if let Some(tt) = tokens.next() {
+            if !is_comma(&tt) {
+                panic!("Expected ',' , found {}", tt);
+            }
+        }
+    }
+    headers
+}
+
+/// Parses `("Retry-After") = 0` into a lowercase header name and a field index.
+fn parse_field_header(value: proc_macro2::TokenStream) -> (String, usize) {
+    let mut tokens = value.into_iter();
+    let name = match tokens.next() {
+        Some(TokenTree::Group(group)) => {
+            let mut tokens = group.stream().into_iter();
+            let name = header_name(tokens.next());
+            if let Some(tt) = tokens.next() {
+                panic!("Expected a single header name, found {}", tt);
+            }
+            name
+        }
+        tt => panic!("Expected header(\"Name\") = <field>, found {:?}", tt),
+    };
+    match tokens.next() {
+        Some(TokenTree::Punct(ref i)) if i.as_char() == '=' => {}
+        tt => panic!("Expected '=' , found {:?}", tt),
+    };
+    let field = parse_int(tokens.collect());
+    match usize::try_from(field) {
+        Ok(field) => (name, field),
+        Err(_) => panic!("Expected a field index, found {}", field),
+    }
+}
+
+fn validate_header_value(value: &str) {
+    if !value
+        .bytes()
+        .all(|b| b == b'\t' || (0x20..0x7F).contains(&b))
+    {
+        panic!("'{}' is not a valid header value", value);
+    }
+}
+
+fn header_name(tt: Option<TokenTree>) -> String {
+    let name = match tt {
+        Some(TokenTree::Literal(ref i)) => match StringLit::try_from(i) {
+            Err(e) => panic!("{}", e.to_compile_error()),
+            Ok(lit) => lit.value().to_string(),
+        },
+        tt => panic!("Expected header name, found {:?}", tt),
+    };
+    let is_token = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+    if name.is_empty() || !name.chars().all(is_token) {
+        panic!("'{}' is not a valid header name", name);
+    }
+    name.to_ascii_lowercase()
+}
+
+const GRPC_CODES: &[&str] = &[
+    "Ok",
+    "Cancelled",
+    "Unknown",
+    "InvalidArgument",
+    "DeadlineExceeded",
+    "NotFound",
+    "AlreadyExists",
+    "PermissionDenied",
+    "ResourceExhausted",
+    "FailedPrecondition",
+    "Aborted",
+    "OutOfRange",
+    "Unimplemented",
+    "Internal",
+    "Unavailable",
+    "DataLoss",
+    "Unauthenticated",
+];
@@ -0,0 +1,23 @@
+use std::backtrace::{Backtrace, BacktraceStatus};
+
+use crate::format::ErrorContext;
+
+/// Text of the #[error(backtrace)] field, `None` when capturing was disabled.
+pub fn captured_backtrace(backtrace: &Backtrace) -> Option<String> {
+    match backtrace.status() {
+        BacktraceStatus::Captured => Some(backtrace.to_string()),
+        _ => None,
+    }
+}
+
+/// Logs where an error carrying a backtrace was created.
+pub fn log_backtrace(error: &ErrorContext) {
+    if let Some(backtrace) = &error.backtrace {
+        log::error!(
+            "{} ({}) was created at:\n{}",
+            error.variant,
+            error.status,
+            backtrace
+        );
+    }
+}
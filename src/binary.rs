@@ -0,0 +1,30 @@
+use axum::http::HeaderValue;
+
+use crate::format::{ErrorBody, ErrorContext, ErrorFormatter};
+
+/// "application/msgpack": the "application/json" body encoded as MessagePack.
+#[cfg(feature = "msgpack")]
+pub struct MsgPack;
+
+#[cfg(feature = "msgpack")]
+impl ErrorFormatter for MsgPack {
+    fn format(&self, error: &ErrorContext) -> ErrorBody {
+        let bytes =
+            rmp_serde::to_vec_named(&error.json()).expect("JSON values are always serializable");
+        ErrorBody::new(HeaderValue::from_static("application/msgpack"), bytes)
+    }
+}
+
+/// "application/cbor": the "application/json" body encoded as CBOR.
+#[cfg(feature = "cbor")]
+pub struct Cbor;
+
+#[cfg(feature = "cbor")]
+impl ErrorFormatter for Cbor {
+    fn format(&self, error: &ErrorContext) -> ErrorBody {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&error.json(), &mut bytes)
+            .expect("JSON values are always serializable");
+        ErrorBody::new(HeaderValue::from_static("application/cbor"), bytes)
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::OnceLock;
+
+use crate::format::ErrorContext;
+use crate::negotiate::FORMATS;
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Process-wide settings of derived errors, installed once with [`configure`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Config {
+    /// Format of enums without #[error_format(...)], "text/plain" when `None`.
+    pub format: Option<&'static str>,
+    /// Whether #[error(details)] fields are sent in response bodies.
+    pub include_details: bool,
+    /// Whether 4xx errors also mark the OpenTelemetry span as failed, not only 5xx errors.
+    pub client_span_errors: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            format: None,
+            include_details: true,
+            client_span_errors: false,
+        }
+    }
+}
+
+impl Config {
+    /// Sets the format of enums without #[error_format(...)].
+    pub fn format(mut self, format: &'static str) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Sets whether #[error(details)] fields are sent in response bodies.
+    pub fn include_details(mut self, include_details: bool) -> Self {
+        self.include_details = include_details;
+        self
+    }
+
+    /// Sets whether 4xx errors also mark the OpenTelemetry span as failed, not only 5xx errors.
+    pub fn client_span_errors(mut self, client_span_errors: bool) -> Self {
+        self.client_span_errors = client_span_errors;
+        self
+    }
+}
+
+/// Installs the process-wide [`Config`], usually at startup.
+///
+/// ```rust
+/// use axum_error_macro::Config;
+///
+/// let production = true;
+/// axum_error_macro::configure(
+///     Config::default()
+///         .format(if production { "text/plain" } else { "application/json" })
+///         .include_details(!production),
+/// );
+/// ```
+///
+/// # Panics
+///
+/// Panics when called more than once or when `format` is not a format that can be
+/// negotiated, such as "application/json" or "text/html".
+pub fn configure(config: Config) {
+    if let Some(format) = config.format {
+        if !FORMATS.contains(&format) {
+            panic!("\"{}\" can not be configured as the default format", format);
+        }
+    }
+    if CONFIG.set(config).is_err() {
+        panic!("axum_error_macro::configure must be called at most once");
+    }
+}
+
+/// Format of enums without #[error_format(...)].
+pub fn configured_format() -> &'static str {
+    CONFIG
+        .get()
+        .and_then(|config| config.format)
+        .unwrap_or("text/plain")
+}
+
+#[cfg(feature = "opentelemetry")]
+pub fn client_span_errors() -> bool {
+    CONFIG.get().is_some_and(|config| config.client_span_errors)
+}
+
+/// Drops what the [`Config`] keeps out of response bodies.
+pub fn apply_config(error: &mut ErrorContext) {
+    if CONFIG.get().is_some_and(|config| !config.include_details) {
+        error.details = None;
+    }
+}
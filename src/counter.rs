@@ -0,0 +1,17 @@
+use crate::format::ErrorContext;
+
+/// Increments the `metric` counter of an error turned into a response, labelled with the
+/// enum, the variant and the status code.
+#[cfg(feature = "metrics")]
+pub fn count_error(metric: &'static str, enumeration: &'static str, error: &ErrorContext) {
+    metrics::counter!(
+        metric,
+        "enum" => enumeration,
+        "variant" => error.variant,
+        "status" => error.status.as_u16().to_string(),
+    )
+    .increment(1);
+}
+
+#[cfg(not(feature = "metrics"))]
+pub fn count_error(_metric: &'static str, _enumeration: &'static str, _error: &ErrorContext) {}
@@ -0,0 +1,30 @@
+use axum::http::StatusCode;
+
+/// Record of the derived error that produced a response, stored in its extensions so that
+/// layers can tell which error they are looking at.
+///
+/// ```rust
+/// use axum_error_macro::{ErrorRecord, ErrorResponse};
+///
+/// #[derive(ErrorResponse)]
+/// #[error_format("application/json")]
+/// enum Error {
+///     #[error(code = 404, msg = "User was not found")]
+///     UserNotFound,
+/// }
+///
+/// let response = Error::UserNotFound.into_response();
+/// let record = response.extensions().get::<ErrorRecord>().unwrap();
+/// assert_eq!(record.variant, "UserNotFound");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ErrorRecord {
+    /// Name of the enum.
+    pub error: &'static str,
+    /// Name of the enum variant.
+    pub variant: &'static str,
+    /// `error_code = "..."` of the variant.
+    pub error_code: Option<&'static str>,
+    /// HTTP status of the response.
+    pub status: StatusCode,
+}
@@ -0,0 +1,407 @@
+use axum::body::{boxed, Full};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::Response;
+use serde_json::{json, Value};
+
+use crate::rpc::grpc_code;
+
+/// Everything a derived error knows when its response body is rendered.
+#[non_exhaustive]
+pub struct ErrorContext {
+    /// HTTP status of the response.
+    pub status: StatusCode,
+    /// `msg` template filled with the variant's fields.
+    pub message: String,
+    /// Name of the enum variant.
+    pub variant: &'static str,
+    /// The `#[error(details)]` field serialized with serde.
+    pub details: Option<Value>,
+    /// `title = "..."` of the variant.
+    pub title: Option<&'static str>,
+    /// The `#[error(pointer)]` field.
+    pub pointer: Option<String>,
+    /// `rpc_code = ...` of the variant.
+    pub rpc_code: Option<i64>,
+    /// `oauth_error = "..."` of the variant.
+    pub oauth_error: Option<&'static str>,
+    /// `error_uri = "..."` of the variant.
+    pub error_uri: Option<&'static str>,
+    /// `grpc = "..."` of the variant.
+    pub grpc: Option<i32>,
+    /// `internal = "..."` of the variant filled with its fields, meant for logs. The built-in
+    /// formats only send it with the `debug-details` feature.
+    pub internal: Option<String>,
+    /// The #[error(backtrace)] field, when it was captured. The built-in formats only send it
+    /// with the `debug-details` feature.
+    pub backtrace: Option<String>,
+    /// ID of the request, for enums with `#[error(request_id = "...")]`.
+    pub request_id: Option<String>,
+    /// `error_code = "..."` of the variant, a stable code for clients and logs.
+    pub error_code: Option<&'static str>,
+}
+
+impl ErrorContext {
+    /// Context of `variant` answering with `status` and `message`, with every optional member
+    /// unset.
+    pub fn new(status: StatusCode, variant: &'static str, message: impl Into<String>) -> Self {
+        ErrorContext {
+            status,
+            message: message.into(),
+            variant,
+            details: None,
+            title: None,
+            pointer: None,
+            rpc_code: None,
+            oauth_error: None,
+            error_uri: None,
+            grpc: None,
+            internal: None,
+            backtrace: None,
+            request_id: None,
+            error_code: None,
+        }
+    }
+
+    /// gRPC status code of the error, mapped from the HTTP status unless the variant sets `grpc`.
+    pub fn grpc_code(&self) -> i32 {
+        self.grpc.unwrap_or_else(|| grpc_code(self.status.as_u16()))
+    }
+
+    /// `{"message": ..., "code": ..., "details": ...}` body of "application/json" and the binary
+    /// formats.
+    pub fn json(&self) -> Value {
+        let mut body = json!({
+          "message": self.message,
+        });
+        if let Some(error_code) = self.error_code {
+            body["code"] = error_code.into();
+        }
+        if let Some(details) = &self.details {
+            body["details"] = details.clone();
+        }
+        if let Some(request_id) = &self.request_id {
+            body["request_id"] = request_id.as_str().into();
+        }
+        if let Some(internal) = self.debug_internal() {
+            body["internal"] = internal.into();
+        }
+        if let Some(backtrace) = self.debug_backtrace() {
+            body["backtrace"] = backtrace.into();
+        }
+        body
+    }
+
+    /// Internal message when the `debug-details` feature allows sending it.
+    pub fn debug_internal(&self) -> Option<&str> {
+        if cfg!(feature = "debug-details") {
+            self.internal.as_deref()
+        } else {
+            None
+        }
+    }
+
+    /// Backtrace when the `debug-details` feature allows sending it.
+    pub fn debug_backtrace(&self) -> Option<&str> {
+        if cfg!(feature = "debug-details") {
+            self.backtrace.as_deref()
+        } else {
+            None
+        }
+    }
+}
+
+/// Rendered response body with its `Content-Type` and any extra headers.
+pub struct ErrorBody {
+    pub content_type: HeaderValue,
+    pub headers: HeaderMap,
+    pub bytes: Vec<u8>,
+}
+
+impl ErrorBody {
+    pub fn new(content_type: HeaderValue, bytes: impl Into<Vec<u8>>) -> Self {
+        ErrorBody {
+            content_type,
+            headers: HeaderMap::new(),
+            bytes: bytes.into(),
+        }
+    }
+}
+
+/// Renders the body of a derived error.
+///
+/// Implemented by the built-in [formats](crate::formats) and by any
+/// `Fn(&ErrorContext) -> ErrorBody`, so a function can be plugged in with
+/// `#[error_format(with = path::to::render)]`.
+///
+/// ```rust
+/// use axum::http::HeaderValue;
+/// use axum_error_macro::{ErrorBody, ErrorContext, ErrorResponse};
+///
+/// fn render(error: &ErrorContext) -> ErrorBody {
+///     let body = format!("{}: {}", error.variant, error.message);
+///     ErrorBody::new(HeaderValue::from_static("text/plain; charset=utf-8"), body)
+/// }
+///
+/// #[derive(ErrorResponse)]
+/// #[error_format(with = render)]
+/// enum Error {
+///     #[error(code = 404, msg = "User was not found")]
+///     UserNotFound,
+/// }
+/// ```
+pub trait ErrorFormatter {
+    fn format(&self, error: &ErrorContext) -> ErrorBody;
+}
+
+impl<F> ErrorFormatter for F
+where
+    F: Fn(&ErrorContext) -> ErrorBody,
+{
+    fn format(&self, error: &ErrorContext) -> ErrorBody {
+        self(error)
+    }
+}
+
+/// Builds the response of a derived error from its rendered body and the variant's headers.
+pub fn respond(status: StatusCode, headers: HeaderMap, body: ErrorBody) -> Response {
+    let mut response = Response::new(boxed(Full::from(body.bytes)));
+    *response.status_mut() = status;
+    let response_headers = response.headers_mut();
+    response_headers.insert(header::CONTENT_TYPE, body.content_type);
+    response_headers.extend(body.headers);
+    response_headers.extend(headers);
+    response
+}
+
+/// "application/json": `{"message": ..., "details": ...}`.
+pub struct Json;
+
+impl ErrorFormatter for Json {
+    fn format(&self, error: &ErrorContext) -> ErrorBody {
+        ErrorBody::new(
+            HeaderValue::from_static("application/json"),
+            error.json().to_string(),
+        )
+    }
+}
+
+/// "text/plain": the bare message.
+pub struct PlainText;
+
+impl ErrorFormatter for PlainText {
+    fn format(&self, error: &ErrorContext) -> ErrorBody {
+        let mut text = error.message.clone();
+        for debug in [error.debug_internal(), error.debug_backtrace()]
+            .into_iter()
+            .flatten()
+        {
+            text.push_str("\n\n");
+            text.push_str(debug);
+        }
+        ErrorBody::new(HeaderValue::from_static("text/plain; charset=utf-8"), text)
+    }
+}
+
+/// "text/html": a minimal page, or the page returned by `template` from the HTML-escaped message.
+#[derive(Default)]
+pub struct Html {
+    pub template: Option<fn(StatusCode, &str) -> String>,
+}
+
+impl ErrorFormatter for Html {
+    fn format(&self, error: &ErrorContext) -> ErrorBody {
+        let page = match self.template {
+            Some(template) => template(error.status, &escape_html(&error.message)),
+            None => {
+                let status = format!(
+                    "{} {}",
+                    error.status.as_u16(),
+                    error.status.canonical_reason().unwrap_or_default()
+                );
+                let status = status.trim_end();
+                format!(
+                    "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{}</title></head>\n<body>\n<h1>{}</h1>\n<p>{}</p>\n</body>\n</html>\n",
+                    status,
+                    status,
+                    escape_html(&error.message)
+                )
+            }
+        };
+        ErrorBody::new(HeaderValue::from_static("text/html; charset=utf-8"), page)
+    }
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#x27;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// "application/xml": `<error><status>..</status><code>..</code><message>..</message></error>`
+/// under `root`, with `code` only when the variant sets `error_code`.
+pub struct Xml {
+    pub root: &'static str,
+}
+
+impl Default for Xml {
+    fn default() -> Self {
+        Xml { root: "error" }
+    }
+}
+
+impl ErrorFormatter for Xml {
+    fn format(&self, error: &ErrorContext) -> ErrorBody {
+        let mut xml = format!("<{}><status>{}</status>", self.root, error.status.as_u16());
+        if let Some(error_code) = error.error_code {
+            xml.push_str(&format!("<code>{}</code>", escape_xml(error_code)));
+        }
+        xml.push_str(&format!(
+            "<message>{}</message>",
+            escape_xml(&error.message)
+        ));
+        if let Some(details) = &error.details {
+            write_xml(&mut xml, "details", details);
+        }
+        if let Some(request_id) = &error.request_id {
+            xml.push_str(&format!(
+                "<request_id>{}</request_id>",
+                escape_xml(request_id)
+            ));
+        }
+        if let Some(internal) = error.debug_internal() {
+            xml.push_str(&format!("<internal>{}</internal>", escape_xml(internal)));
+        }
+        if let Some(backtrace) = error.debug_backtrace() {
+            xml.push_str(&format!("<backtrace>{}</backtrace>", escape_xml(backtrace)));
+        }
+        xml.push_str(&format!("</{}>", self.root));
+        ErrorBody::new(HeaderValue::from_static("application/xml"), xml)
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn write_xml(xml: &mut String, name: &str, value: &Value) {
+    let name: String = name
+        .chars()
+        .enumerate()
+        .map(|(index, c)| match c {
+            c if c.is_ascii_alphabetic() || c == '_' => c,
+            c if index > 0 && (c.is_ascii_digit() || c == '-' || c == '.') => c,
+            _ => '_',
+        })
+        .collect();
+    let name = if name.is_empty() { "_".into() } else { name };
+    xml.push_str(&format!("<{}>", name));
+    match value {
+        Value::Null => {}
+        Value::String(value) => xml.push_str(&escape_xml(value)),
+        Value::Array(values) => {
+            for value in values {
+                write_xml(xml, "item", value);
+            }
+        }
+        Value::Object(values) => {
+            for (name, value) in values {
+                write_xml(xml, name, value);
+            }
+        }
+        value => xml.push_str(&value.to_string()),
+    }
+    xml.push_str(&format!("</{}>", name));
+}
+
+/// "application/vnd.api+json": a JSON:API errors document.
+pub struct JsonApi;
+
+impl ErrorFormatter for JsonApi {
+    fn format(&self, error: &ErrorContext) -> ErrorBody {
+        let mut object = json!({
+          "status": error.status.as_str(),
+          "detail": error.message,
+        });
+        if let Some(error_code) = error.error_code {
+            object["code"] = error_code.into();
+        }
+        if let Some(title) = error.title.or(error.status.canonical_reason()) {
+            object["title"] = title.into();
+        }
+        if let Some(pointer) = &error.pointer {
+            object["source"] = json!({ "pointer": pointer });
+        }
+        if let Some(details) = &error.details {
+            object["meta"] = json!({ "details": details });
+        }
+        if let Some(internal) = error.debug_internal() {
+            object["meta"]["internal"] = internal.into();
+        }
+        if let Some(backtrace) = error.debug_backtrace() {
+            object["meta"]["backtrace"] = backtrace.into();
+        }
+        let mut document = json!({ "errors": [object] });
+        if let Some(request_id) = &error.request_id {
+            document["meta"] = json!({ "request_id": request_id });
+        }
+        ErrorBody::new(
+            HeaderValue::from_static("application/vnd.api+json"),
+            document.to_string(),
+        )
+    }
+}
+
+/// "application/problem+json": an RFC 9457 problem details object.
+pub struct ProblemJson;
+
+impl ErrorFormatter for ProblemJson {
+    fn format(&self, error: &ErrorContext) -> ErrorBody {
+        let mut problem = json!({
+          "type": "about:blank",
+          "status": error.status.as_u16(),
+          "detail": error.message,
+        });
+        if let Some(title) = error.title.or(error.status.canonical_reason()) {
+            problem["title"] = title.into();
+        }
+        if let Some(error_code) = error.error_code {
+            problem["code"] = error_code.into();
+        }
+        if let Some(details) = &error.details {
+            problem["details"] = details.clone();
+        }
+        if let Some(request_id) = &error.request_id {
+            problem["request_id"] = request_id.as_str().into();
+        }
+        if let Some(internal) = error.debug_internal() {
+            problem["internal"] = internal.into();
+        }
+        if let Some(backtrace) = error.debug_backtrace() {
+            problem["backtrace"] = backtrace.into();
+        }
+        ErrorBody::new(
+            HeaderValue::from_static("application/problem+json"),
+            problem.to_string(),
+        )
+    }
+}
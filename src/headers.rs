@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method};
+
+/// Values of fields sent as response headers with `header("...") = <field>`.
+pub trait ToHeaderValue {
+    /// The header value, `None` when it can not be represented.
+    fn to_header_value(&self) -> Option<HeaderValue>;
+}
+
+/// Whole seconds, as used by `Retry-After` and `Cache-Control: max-age`.
+impl ToHeaderValue for Duration {
+    fn to_header_value(&self) -> Option<HeaderValue> {
+        Some(self.as_secs().into())
+    }
+}
+
+impl ToHeaderValue for str {
+    fn to_header_value(&self) -> Option<HeaderValue> {
+        HeaderValue::from_str(self).ok()
+    }
+}
+
+impl ToHeaderValue for String {
+    fn to_header_value(&self) -> Option<HeaderValue> {
+        self.as_str().to_header_value()
+    }
+}
+
+impl ToHeaderValue for HeaderValue {
+    fn to_header_value(&self) -> Option<HeaderValue> {
+        Some(self.clone())
+    }
+}
+
+impl ToHeaderValue for Method {
+    fn to_header_value(&self) -> Option<HeaderValue> {
+        self.as_str().to_header_value()
+    }
+}
+
+/// Comma separated list, as used by `Allow`.
+impl<T: ToHeaderValue> ToHeaderValue for [T] {
+    fn to_header_value(&self) -> Option<HeaderValue> {
+        let mut list = Vec::new();
+        for value in self {
+            if !list.is_empty() {
+                list.extend_from_slice(b", ");
+            }
+            list.extend_from_slice(value.to_header_value()?.as_bytes());
+        }
+        HeaderValue::from_bytes(&list).ok()
+    }
+}
+
+impl<T: ToHeaderValue> ToHeaderValue for Vec<T> {
+    fn to_header_value(&self) -> Option<HeaderValue> {
+        self.as_slice().to_header_value()
+    }
+}
+
+impl<T: ToHeaderValue + ?Sized> ToHeaderValue for &T {
+    fn to_header_value(&self) -> Option<HeaderValue> {
+        (**self).to_header_value()
+    }
+}
+
+macro_rules! integer_header_value {
+    ($($integer:ty),*) => {
+        $(impl ToHeaderValue for $integer {
+            fn to_header_value(&self) -> Option<HeaderValue> {
+                Some((*self).into())
+            }
+        })*
+    };
+}
+
+integer_header_value!(u16, i16, u32, i32, u64, i64, usize, isize);
+
+/// Inserts a header taken from a field, dropping values that are not valid header values.
+pub fn insert_header<T: ToHeaderValue + ?Sized>(
+    headers: &mut HeaderMap,
+    name: HeaderName,
+    value: &T,
+) {
+    match value.to_header_value() {
+        Some(value) => {
+            headers.insert(name, value);
+        }
+        None => log::warn!("Dropping invalid value of the '{}' error header", name),
+    }
+}
@@ -3,7 +3,7 @@
 //! ## Example:
 //! ```rust
 //! use axum_error_macro::ErrorResponse;
-//!	use axum::response::Response;
+//! use axum::response::Response;
 //!
 //! #[derive(ErrorResponse)]
 //! #[error_format("application/json")]
@@ -51,7 +51,8 @@
 //
 //! ```
 //! Also you can configure error response format with #[error_format(...)] macro.
-//! Today only "application/json" and "text/plain" are available.
+//! Today only "application/json", "text/plain", "text/html", "application/xml",
+//! "application/vnd.api+json" and "application/problem+json" are available.
 //!
 //! Returned response will be in this format:
 //!
@@ -65,204 +66,445 @@
 //! ```bash
 //! Internal Server Error!!!
 //! ```
+//!
+//! or
+//!
+//! ```xml
+//! <error><status>500</status><message>Internal Server Error!!!</message></error>
+//! ```
+//!
+//! The root element of "application/xml" can be renamed with #[error_format("application/xml", root = "fault")].
+//! A variant's `error_code = "..."` is sent as a `<code>` element after the status, and as `code`
+//! in "application/json", "application/problem+json", MessagePack and CBOR bodies.
+//!
+//! A variant can pin its own format with `format = "..."`, overriding #[error_format(...)]
+//! and the negotiated format:
+//!
+//! ```rust
+//! use axum_error_macro::ErrorResponse;
+//!
+//! #[derive(ErrorResponse)]
+//! #[error_format("application/json")]
+//! enum Error {
+//!   #[error(code = 404, msg = "User was not found")]
+//!   UserNotFound,
+//!
+//!   #[error(code = 503, msg = "Unhealthy", format = "text/plain")]
+//!   Unhealthy
+//! }
+//! ```
+//!
+//! Variants can send extra response headers, either fixed with `headers("Name" = "value", ...)` or
+//! taken from a field with `header("Name") = <field index>`. Header fields are not passed to the message
+//! template. They can be strings, integers, `HeaderValue`s or `Duration`s (sent as whole seconds),
+//! and values that are not valid header values are dropped with a warning logged through `log`.
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use axum_error_macro::ErrorResponse;
+//!
+//! #[derive(ErrorResponse)]
+//! enum Error {
+//!   #[error(code = 429, msg = "Too many requests", headers("Retry-After" = "30"))]
+//!   TooManyRequests,
+//!
+//!   #[error(code = 503, msg = "{} is unavailable", header("Retry-After") = 1)]
+//!   Unavailable(String, Duration)
+//! }
+//! ```
+//!
+//! HTTP requires `WWW-Authenticate` on 401 and `Allow` on 405 responses. They are set with
+//! `auth_challenge = "Bearer realm=\"api\""` and `allow = "GET, POST"`, or `allow = <field index>` for
+//! a field holding a `Vec<Method>`. The derive warns about 401 and 405 variants without them
+//! ("oauth2" variants get their challenge from the format).
+//!
+//! ```rust
+//! use axum::http::Method;
+//! use axum_error_macro::ErrorResponse;
+//!
+//! #[derive(ErrorResponse)]
+//! enum Error {
+//!   #[error(code = 401, msg = "Unauthorized", auth_challenge = "Bearer realm=\"api\"")]
+//!   Unauthorized,
+//!
+//!   #[error(code = 405, msg = "Method is not allowed", allow = 0)]
+//!   MethodNotAllowed(Vec<Method>)
+//! }
+//! ```
+//!
+//! Error responses are sent with `Cache-Control: no-store` so intermediaries never cache them. The
+//! policy can be changed for the whole enum with #[error(cache = "...")] and for a single variant
+//! with `cache = "..."`. "text/plain" and "text/html" bodies are sent with `charset=utf-8`.
+//!
+//! ```rust
+//! use axum_error_macro::ErrorResponse;
+//!
+//! #[derive(ErrorResponse)]
+//! #[error(cache = "no-cache")]
+//! enum Error {
+//!   #[error(code = 500, msg = "Internal Server Error!!!")]
+//!   Internal,
+//!
+//!   #[error(code = 404, msg = "Page was not found", cache = "public, max-age=60")]
+//!   PageNotFound
+//! }
+//! ```
+//!
+//! Details meant for logs rather than clients go in `internal = "..."`, which can refer to any field
+//! by index. Only `msg` is sent, and the generated `internal_message()` returns the internal text. With
+//! the `debug-details` feature, for development builds, the built-in formats send it as well
+//! (`internal` in JSON and XML bodies, after the message in "text/plain").
+//! `{0}` names the variant's field 0 in both options. `msg` rejects an index of a field that is
+//! not formatted, such as a `details` or header field.
+//!
+//! ```rust
+//! use axum_error_macro::ErrorResponse;
+//!
+//! #[derive(ErrorResponse)]
+//! enum Error {
+//!   #[error(code = 500, msg = "Internal error", internal = "db failure: {0}")]
+//!   Database(String)
+//! }
+//!
+//! let error = Error::Database("connection reset".into());
+//! assert_eq!(error.internal_message().unwrap(), "db failure: connection reset");
+//! ```
+//!
+//! Fields marked with `#[sensitive]` are written as `***` wherever the message template uses them,
+//! so tokens or emails never reach a body (or a gRPC/GraphQL message). `internal = "..."` still
+//! receives the raw value.
+//!
+//! ```rust
+//! use axum_error_macro::ErrorResponse;
+//!
+//! #[derive(ErrorResponse)]
+//! enum Error {
+//!   #[error(code = 401, msg = "Token {} is invalid", internal = "token {0} is invalid", auth_challenge = "Bearer")]
+//!   InvalidToken(#[sensitive] String)
+//! }
+//! ```
+//!
+//! A `std::backtrace::Backtrace` field marked with #[error(backtrace)] records where a 5xx error was
+//! created. The derive generates a snake_case constructor capturing it (`Error::database_failure(...)`),
+//! and the backtrace is logged through `log` when the error is turned into a response (capturing follows
+//! `RUST_BACKTRACE`). It is only sent in bodies with the `debug-details` feature.
+//!
+//! ```rust
+//! use std::backtrace::Backtrace;
+//! use axum_error_macro::ErrorResponse;
+//!
+//! #[derive(ErrorResponse)]
+//! enum Error {
+//!   #[error(code = 500, msg = "Internal error", internal = "db failure: {0}")]
+//!   DatabaseFailure(String, #[error(backtrace)] Backtrace)
+//! }
+//!
+//! let error = Error::database_failure("connection reset".into());
+//! ```
+//!
+//! With the `tracing` feature every error turned into a response emits an event with its `status`,
+//! `variant`, `error_code`, message and `internal` text: ERROR for 5xx, WARN for 4xx and DEBUG
+//! otherwise, or the level set on the variant with `log = "info"`. `status`, `variant`, `error_code`
+//! and `message` are also recorded on the current span when it declares them. `error_code` is a
+//! stable code set on the variant with `error_code = "USER_EXISTS"`.
+//!
+//! ```rust,ignore
+//! #[error(code = 409, msg = "User already exists", error_code = "USER_EXISTS", log = "info")]
+//! UserExists
+//! ```
+//!
+//! With the `metrics` feature every error turned into a response increments a `http_errors_total`
+//! counter through the `metrics` facade, labelled with `enum`, `variant` and `status`. Labels never
+//! contain field values, so their cardinality stays bounded. The enum can rename the counter with
+//! #[error(metric = "user_errors_total")].
+//!
+//! With the `opentelemetry` feature a 5xx error turned into a response sets the status of the active
+//! OpenTelemetry span to Error and adds an `exception` event with `exception.type` (`Error::Variant`)
+//! and `exception.message`. 4xx errors leave the span alone unless `client_span_errors(true)`
+//! is set in the [`Config`].
+//!
+//! Binary bodies with the same shape as "application/json" are available behind cargo features:
+//! "application/msgpack" with the `msgpack` feature and "application/cbor" with the `cbor` feature.
+//!
+//! "application/vnd.api+json" renders a JSON:API errors document. The message becomes `detail`,
+//! `title` defaults to the reason phrase and can be set per variant, `error_code` becomes `code`,
+//! and a field marked with #[error(pointer)] becomes `source.pointer`:
+//!
+//! ```rust
+//! use axum_error_macro::ErrorResponse;
+//!
+//! #[derive(ErrorResponse)]
+//! #[error_format("application/vnd.api+json")]
+//! enum Error {
+//!   #[error(code = 422, msg = "{} must not be empty", title = "Invalid Attribute")]
+//!   EmptyAttribute(String, #[error(pointer)] String)
+//! }
+//! ```
+//!
+//! ```json
+//! {
+//!   "errors": [{
+//!     "status": "422",
+//!     "title": "Invalid Attribute",
+//!     "detail": "email must not be empty",
+//!     "source": { "pointer": "/data/attributes/email" }
+//!   }]
+//! }
+//! ```
+//!
+//! "application/problem+json" renders an RFC 9457 problem details object. `type` is "about:blank",
+//! `title` defaults to the reason phrase and can be set per variant, and the message becomes `detail`:
+//!
+//! ```json
+//! {
+//!   "type": "about:blank",
+//!   "title": "Not Found",
+//!   "status": 404,
+//!   "detail": "User by 12 id was not found"
+//! }
+//! ```
+//!
+//! "json-rpc" renders a JSON-RPC 2.0 error object as "application/json". Variants pick their code
+//! with `rpc_code = -32602` (defaulting to -32603 for 5xx and -32000 otherwise), the #[error(details)]
+//! field becomes `data`, and `id` is filled when the handler runs through `with_rpc_id`:
+//!
+//! ```json
+//! {"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid params","data":["username"]},"id":7}
+//! ```
+//!
+//! "oauth2" renders an OAuth 2.0 error response (RFC 6749) as "application/json". Every variant
+//! names its error with `oauth_error = "..."` and may set `error_uri = "..."`, the message becomes
+//! `error_description`, and 401 variants also get a `WWW-Authenticate: Bearer error="..."` header.
+//!
+//! ```rust
+//! use axum_error_macro::ErrorResponse;
+//!
+//! #[derive(ErrorResponse)]
+//! #[error_format("oauth2")]
+//! enum Error {
+//!   #[error(code = 400, msg = "Grant has expired", oauth_error = "invalid_grant")]
+//!   ExpiredGrant,
+//!
+//!   #[error(code = 401, msg = "Access token has expired", oauth_error = "invalid_token")]
+//!   ExpiredToken
+//! }
+//! ```
+//!
+//! "connect" renders a Connect protocol error (`{"code":"not_found","message":"..."}`) as
+//! "application/json", using the same HTTP to gRPC code mapping and `grpc = "..."` override as the
+//! `tonic` feature. With the `prost` feature the #[error(details)] field is added to `details` as a
+//! `google.protobuf.Value`, and "application/x-protobuf" renders a binary `google.rpc.Status`.
+//!
+//! With the `tonic` feature the derive also generates `From<Error> for tonic::Status`, so the same
+//! enum can be returned from gRPC services. The message template becomes the status message and
+//! the HTTP code is mapped to the closest gRPC code (404 to NotFound, 409 to AlreadyExists,
+//! 401 to Unauthenticated, ...). A variant can pick its own code with `grpc = "..."`:
+//!
+//! ```rust,ignore
+//! #[error(code = 400, msg = "Account {} is locked", grpc = "FailedPrecondition")]
+//! AccountLocked(u32)
+//! ```
+//!
+//! With the `async-graphql` feature the derive implements `async_graphql::ErrorExtensions` and
+//! `From<Error> for async_graphql::Error`. The message template becomes the GraphQL error message
+//! and the `status` and `variant` extensions are set, plus `code` for variants with an `error_code`.
+//!
+//! "text/html" renders a minimal page with the status, reason phrase and HTML-escaped message.
+//! A custom page can be rendered by pointing at a function (which may call askama, minijinja, ...)
+//! receiving the status code and the HTML-escaped message, so it can be inserted as is.
+//!
+//! ```rust
+//! use axum::http::StatusCode;
+//! use axum_error_macro::ErrorResponse;
+//!
+//! fn render(code: StatusCode, msg: &str) -> String {
+//!   format!("<h1>{}</h1><p>{}</p>", code, msg)
+//! }
+//!
+//! #[derive(ErrorResponse)]
+//! #[error_format("text/html", template = render)]
+//! enum Error {
+//!   #[error(code = 404, msg = "Page was not found")]
+//!   NotFound
+//! }
+//! ```
+//!
+//! Structured data can be sent next to the message by marking a field with #[error(details)].
+//! It is serialized with serde into the "details" member of JSON and XML responses and skipped
+//! by "text/plain" and "text/html". In XML, array items are written as `<item>` elements. The
+//! marked field is not passed to the message template.
+//!
+//! ```rust
+//! use axum_error_macro::ErrorResponse;
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! struct FieldError {
+//!   field: String,
+//!   reason: String
+//! }
+//!
+//! #[derive(ErrorResponse)]
+//! #[error_format("application/json")]
+//! enum Error {
+//!   #[error(code = 422, msg = "Validation failed")]
+//!   InvalidFields(#[error(details)] Vec<FieldError>)
+//! }
+//! ```
+//!
+//! ```json
+//! {
+//!   "message": "Validation failed",
+//!   "details": [{ "field": "email", "reason": "must not be empty" }]
+//! }
+//! ```
+//!
+//! Any other body can be rendered by a custom formatter with #[error_format(with = ...)]. It is either
+//! a function taking an [`ErrorContext`] (status, message, variant name, details, ...) and returning an
+//! [`ErrorBody`], or a value implementing [`ErrorFormatter`]. The built-in formats are available in
+//! [`formats`] to be wrapped. Custom formats are never negotiated.
+//! [`ErrorContext`] is non-exhaustive, so formatters can be tested on one built with
+//! [`ErrorContext::new`].
+//!
+//! ```rust
+//! use axum::http::HeaderValue;
+//! use axum_error_macro::{ErrorBody, ErrorContext, ErrorResponse};
+//!
+//! fn render(error: &ErrorContext) -> ErrorBody {
+//!   let body = serde_json::json!({ "error": error.variant, "message": error.message });
+//!   ErrorBody::new(HeaderValue::from_static("application/json"), body.to_string())
+//! }
+//!
+//! #[derive(ErrorResponse)]
+//! #[error_format(with = render)]
+//! enum Error {
+//!   #[error(code = 404, msg = "User was not found")]
+//!   UserNotFound
+//! }
+//! ```
+//!
+//! The format can also be negotiated per request from its `Accept` header by installing the
+//! [`negotiate`] middleware. The enum's #[error_format(...)] stays the fallback.
+//!
+//! ```rust
+//! use axum::{middleware, routing::get, Router};
+//!
+//! let app: Router = Router::new()
+//!   .route("/", get(|| async { "Hello" }))
+//!   .layer(middleware::from_fn(axum_error_macro::negotiate));
+//! ```
+//!
+//! Enums with #[error(request_id = "X-Request-Id")] send back the ID of the request stored by the
+//! [`request_id`] middleware. It is taken from `x-request-id` when that is at most 128 visible ASCII
+//! characters, or else from the trace ID of a W3C `traceparent`. It is echoed in the named header
+//! and added as `request_id` to JSON, XML and problem+json bodies (`meta.request_id` for JSON:API).
+//!
+//! ```rust
+//! use axum::{middleware, routing::get, Router};
+//! use axum_error_macro::ErrorResponse;
+//!
+//! #[derive(ErrorResponse)]
+//! #[error_format("application/json")]
+//! #[error(request_id = "X-Request-Id")]
+//! enum Error {
+//!   #[error(code = 404, msg = "User was not found")]
+//!   UserNotFound
+//! }
+//!
+//! let app: Router = Router::new()
+//!   .route("/", get(|| async { Error::UserNotFound }))
+//!   .layer(middleware::from_fn(axum_error_macro::request_id));
+//! ```
+//!
+//! Every derived response carries an [`ErrorRecord`] in its extensions with the enum name, variant,
+//! `error_code` and status, so logging or rewriting layers can tell which error produced it. Enums with
+//! #[error(attach)] also store the error itself as an `Arc<Error>`, which requires the enum to be
+//! `Send + Sync + 'static`.
+//!
+//! ```rust
+//! use axum_error_macro::{ErrorRecord, ErrorResponse};
+//! use std::sync::Arc;
+//!
+//! #[derive(ErrorResponse)]
+//! #[error_format("application/json")]
+//! #[error(attach)]
+//! enum Error {
+//!   #[error(code = 404, msg = "User by {} id was not found")]
+//!   UserByIdNotFound(u32)
+//! }
+//!
+//! let response = Error::UserByIdNotFound(12).into_response();
+//! let record = response.extensions().get::<ErrorRecord>().unwrap();
+//! assert_eq!(record.variant, "UserByIdNotFound");
+//! assert!(response.extensions().get::<Arc<Error>>().is_some());
+//! ```
+//!
+//! Enums without #[error_format(...)] use "text/plain" unless a process-wide [`Config`] is installed at
+//! startup with [`configure`], so the same binary can answer with verbose JSON in staging and terse
+//! text in production. `include_details(false)` keeps #[error(details)] fields out of every body.
+//!
+//! ```rust
+//! use axum_error_macro::Config;
+//!
+//! let production = std::env::var("PRODUCTION").is_ok();
+//! axum_error_macro::configure(
+//!   Config::default()
+//!     .format(if production { "text/plain" } else { "application/json" })
+//!     .include_details(!production),
+//! );
+//! ```
 
-use litrs::{IntegerLit, StringLit};
-use proc_macro::TokenStream;
-use proc_macro2::{Ident, TokenTree};
-use quote::quote;
-use syn::punctuated::Punctuated;
-use syn::spanned::Spanned;
-use syn::token::Comma;
-use syn::{Fields, MetaList, Variant};
-
-#[proc_macro_derive(ErrorResponse, attributes(error, error_format))]
-pub fn axum_error_macro_derive(input: TokenStream) -> TokenStream {
-    let ast: syn::DeriveInput = syn::parse(input).unwrap();
-    impl_error(ast)
-}
-
-fn impl_error(ast: syn::DeriveInput) -> TokenStream {
-    let ident = &ast.ident;
-    let eident = Ident::new(&format!("Derive{}", ident.to_string()), ident.span());
-
-    let format = retrieve_format(&ast);
-    let variants = retrieve_variants(&ast);
-    let matches = match_error(ident.clone(), eident.clone(), variants);
-
-    let expanded = quote!(
-          use axum::response::IntoResponse;
-
-          const CONTENT_TYPE: &str = #format;
-
-          struct #eident(axum::http::StatusCode, String);
-
-          impl #eident {
-            pub fn new(code: u16, msg: String) -> Self {
-               let code = axum::http::StatusCode::from_u16(code).expect("Error code must be valid");
-               #eident(code, msg)
-            }
-
-            pub fn generate_response(&self) -> axum::body::Body {
-                let msg = match CONTENT_TYPE {
-                    "application/json" => {
-                        serde_json::json!({
-                          "message": self.1,
-                        }).to_string()
-                    },
-                    "text/plain" => self.1.to_string(),
-                    _ => panic!("Wrong format type")
-                };
-                axum::body::Body::from(msg)
-            }
-          }
-
-          impl axum::response::IntoResponse for #eident {
-            fn into_response(self) -> axum::response::Response {
-                let body = self.generate_response();
-
-                axum::response::Response::builder()
-                  .status(self.0)
-                  .header(
-                    hyper::header::CONTENT_TYPE,
-                    axum::http::HeaderValue::from_static(CONTENT_TYPE),
-                  )
-                  .body(axum::body::boxed(body))
-                  .unwrap()
-            }
-          }
-
-            impl axum::response::IntoResponse for #ident {
-                fn into_response(self) -> axum::response::Response {
-                    let res = match self {
-                      #(#matches),*
-                    };
-                    res.into_response()
-                }
-            }
-    );
-
-    expanded.into()
-}
-
-fn retrieve_format(ast: &syn::DeriveInput) -> String {
-    if let Some(attr) = ast.attrs.get(0) {
-        if let syn::Meta::List(meta_list) = &attr.meta {
-            let format = meta_list.tokens.to_string().replace("\"", "");
-            match format.as_str() {
-                "application/json" | "text/plain" => return format,
-                _ => panic!("Wrong format type"),
-            }
-        }
-    }
-    "text/plain".into()
-}
-
-fn retrieve_variants(ast: &syn::DeriveInput) -> Punctuated<Variant, Comma> {
-    if let syn::Data::Enum(syn::DataEnum { variants, .. }) = &ast.data {
-        return variants.to_owned();
-    } else {
-        panic!("Expected at least one enum variant")
-    };
-}
-
-fn retrieve_params(fields: Fields) -> Option<Vec<proc_macro2::TokenStream>> {
-    if let syn::Fields::Unnamed(pfields) = fields {
-        if pfields.unnamed.len() == 0 {
-            None
-        } else {
-            Some(
-                pfields
-                    .unnamed
-                    .into_iter()
-                    .enumerate()
-                    .map(|(index, field)| {
-                        let unique_param_ident = Ident::new(
-                            &format!("param_{}", char::from_u32(index as u32 + 97).unwrap()),
-                            field.span(),
-                        );
-                        quote!(#unique_param_ident)
-                    })
-                    .collect(),
-            )
-        }
-    } else {
-        None
-    }
-}
-
-fn match_error(
-    ident: proc_macro2::Ident,
-    eident: proc_macro2::Ident,
-    variants: Punctuated<Variant, Comma>,
-) -> Vec<proc_macro2::TokenStream> {
-    variants
-        .iter()
-        .filter_map(|v| {
-            let bident = &v.ident;
-            if let syn::Meta::List(meta_list) = &v.attrs[0].meta {
-                let (error_code, error_msg) = validate_attribute(meta_list);
+mod backtrace;
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+mod binary;
+mod config;
+mod counter;
+mod extension;
+mod format;
+mod headers;
+mod negotiate;
+mod oauth;
+mod otel;
+mod request_id;
+mod rpc;
+mod trace;
 
-                let retrieved_params = retrieve_params(v.fields.clone());
-                let (match_params, format_params) = if let Some(params) = retrieved_params {
-                    (Some(quote!((#(#params),*))), Some(quote!(#(#params),*)))
-                } else {
-                    (None, None)
-                };
+pub use axum_error_macro_derive::ErrorResponse;
+pub use config::{configure, Config};
+pub use extension::ErrorRecord;
+pub use format::{ErrorBody, ErrorContext, ErrorFormatter};
+pub use headers::ToHeaderValue;
+pub use negotiate::negotiate;
+pub use request_id::request_id;
+pub use rpc::with_rpc_id;
 
-                return Some(quote!(
-                  #ident::#bident #match_params => {
-                    #eident::new(#error_code, format!(#error_msg, #format_params))
-                  }
-                ));
-            } else {
-                None
-            }
-        })
-        .collect()
+/// Built-in formats, usable from a custom [`ErrorFormatter`] to wrap or extend them.
+pub mod formats {
+    #[cfg(feature = "cbor")]
+    pub use crate::binary::Cbor;
+    #[cfg(feature = "msgpack")]
+    pub use crate::binary::MsgPack;
+    pub use crate::format::{Html, Json, JsonApi, PlainText, ProblemJson, Xml};
+    pub use crate::oauth::OAuth2;
+    #[cfg(feature = "prost")]
+    pub use crate::rpc::Protobuf;
+    pub use crate::rpc::{Connect, JsonRpc};
 }
 
-fn validate_attribute(meta_list: &MetaList) -> (u16, String) {
-    let mut tokens = meta_list.tokens.to_owned().into_iter();
-    match tokens.next().unwrap() {
-        TokenTree::Ident(ref i) => assert_eq!(i, "code"),
-        tt => panic!("Expected 'code', found {}", tt),
-    };
-    match tokens.next().unwrap() {
-        TokenTree::Punct(ref i) => assert_eq!(i.as_char(), '='),
-        tt => panic!("Expected '=' , found {}", tt),
-    };
-    let error_code: u16 = match tokens.next().unwrap() {
-        TokenTree::Literal(ref i) => {
-            let int_lit = match IntegerLit::try_from(i) {
-                Err(e) => panic!("{}", e.to_compile_error()),
-                Ok(lit) => lit,
-            };
-            int_lit.value().unwrap()
-        }
-        tt => panic!("Expected http code error, found {}", tt),
-    };
-    match tokens.next().unwrap() {
-        TokenTree::Punct(ref i) => assert_eq!(i.as_char(), ','),
-        tt => panic!("Expected ',' , found {}", tt),
-    };
-    match tokens.next().unwrap() {
-        TokenTree::Ident(ref i) => assert_eq!(i, "msg"),
-        tt => panic!("Expected 'msg', found {}", tt),
-    };
-    match tokens.next().unwrap() {
-        TokenTree::Punct(ref i) => assert_eq!(i.as_char(), '='),
-        tt => panic!("Expected '=' , found {}", tt),
-    };
-    let error_msg = match tokens.next().unwrap() {
-        TokenTree::Literal(ref i) => {
-            let string_lit = match StringLit::try_from(i) {
-                Err(e) => panic!("{}", e.to_compile_error()),
-                Ok(lit) => lit,
-            };
-            string_lit.value().to_string()
-        }
-        tt => panic!("Expected error message, found {}", tt),
-    };
-
-    (error_code, error_msg)
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::backtrace::{captured_backtrace, log_backtrace};
+    pub use crate::config::{apply_config, configured_format};
+    pub use crate::counter::count_error;
+    pub use crate::format::respond;
+    pub use crate::headers::insert_header;
+    pub use crate::negotiate::{negotiated_format, FORMATS};
+    pub use crate::otel::mark_span;
+    pub use crate::request_id::current_request_id;
+    pub use crate::trace::trace_error;
+    #[cfg(feature = "async-graphql")]
+    pub use async_graphql;
+    pub use serde_json;
+    #[cfg(feature = "tonic")]
+    pub use tonic;
 }
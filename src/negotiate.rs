@@ -0,0 +1,107 @@
+use axum::http::{header, Request};
+use axum::middleware::Next;
+use axum::response::Response;
+
+/// Formats that can be negotiated or configured as the default, with the binary ones of the
+/// enabled features.
+pub const FORMATS: &[&str] = &[
+    "application/json",
+    "text/plain",
+    "text/html",
+    "application/xml",
+    "application/vnd.api+json",
+    "application/problem+json",
+    #[cfg(feature = "msgpack")]
+    "application/msgpack",
+    #[cfg(feature = "cbor")]
+    "application/cbor",
+    #[cfg(feature = "prost")]
+    "application/x-protobuf",
+];
+
+tokio::task_local! {
+    static ACCEPT: Option<String>;
+}
+
+/// Middleware storing the request's `Accept` header so derived errors returned
+/// while handling it are rendered in the format the client prefers.
+///
+/// ```rust
+/// use axum::{middleware, routing::get, Router};
+///
+/// let app: Router = Router::new()
+///     .route("/", get(|| async { "Hello" }))
+///     .layer(middleware::from_fn(axum_error_macro::negotiate));
+/// ```
+///
+/// The enum's `#[error_format(...)]` is used when no middleware is installed, when the
+/// client accepts anything, and when it accepts none of the available formats, so an
+/// error is never replaced by `406 Not Acceptable`.
+pub async fn negotiate<B>(request: Request<B>, next: Next<B>) -> Response {
+    let accept = request
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .map(str::to_owned);
+    ACCEPT.scope(accept, next.run(request)).await
+}
+
+/// Picks the format among `formats` that the current request accepts with the highest
+/// quality, preferring `fallback` on ties.
+pub fn negotiated_format(formats: &[&'static str], fallback: &'static str) -> &'static str {
+    let accept = match ACCEPT.try_with(|accept| accept.clone()) {
+        Ok(Some(accept)) => accept,
+        _ => return fallback,
+    };
+    let ranges: Vec<(&str, f32)> = accept.split(',').filter_map(parse_range).collect();
+
+    let mut best = (fallback, quality(&ranges, fallback));
+    for format in formats {
+        let quality = quality(&ranges, format);
+        if quality > best.1 {
+            best = (format, quality);
+        }
+    }
+    if best.1 > 0.0 {
+        best.0
+    } else {
+        fallback
+    }
+}
+
+fn parse_range(range: &str) -> Option<(&str, f32)> {
+    let mut params = range.split(';');
+    let media_type = params.next()?.trim();
+    if media_type.is_empty() {
+        return None;
+    }
+    let quality = params
+        .filter_map(|param| param.trim().strip_prefix("q="))
+        .find_map(|q| q.trim().parse().ok())
+        .unwrap_or(1.0);
+    Some((media_type, quality))
+}
+
+/// Quality of the most specific media range matching `format`, 0 if none does.
+fn quality(ranges: &[(&str, f32)], format: &str) -> f32 {
+    let (kind, _) = format.split_once('/').unwrap_or((format, ""));
+    let mut best: Option<(u8, f32)> = None;
+    for (range, quality) in ranges {
+        let specificity = if range.eq_ignore_ascii_case(format) {
+            2
+        } else if range
+            .strip_suffix("/*")
+            .is_some_and(|range| range.eq_ignore_ascii_case(kind))
+        {
+            1
+        } else if *range == "*/*" {
+            0
+        } else {
+            continue;
+        };
+        if best.is_none_or(|(best, _)| specificity > best) {
+            best = Some((specificity, *quality));
+        }
+    }
+    best.map_or(0.0, |(_, quality)| quality)
+}
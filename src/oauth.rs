@@ -0,0 +1,57 @@
+use axum::http::{header, HeaderValue, StatusCode};
+use serde_json::json;
+
+use crate::format::{ErrorBody, ErrorContext, ErrorFormatter};
+
+/// "oauth2": an OAuth 2.0 error response (RFC 6749), sent as "application/json".
+///
+/// 401 errors also get a `WWW-Authenticate: Bearer error="..."` header.
+pub struct OAuth2;
+
+impl ErrorFormatter for OAuth2 {
+    fn format(&self, error: &ErrorContext) -> ErrorBody {
+        let mut body = json!({
+          "error": error.oauth_error,
+          "error_description": error.message,
+        });
+        if let Some(error_uri) = error.error_uri {
+            body["error_uri"] = error_uri.into();
+        }
+        let mut body = ErrorBody::new(
+            HeaderValue::from_static("application/json"),
+            body.to_string(),
+        );
+        if error.status == StatusCode::UNAUTHORIZED {
+            body.headers.insert(
+                header::WWW_AUTHENTICATE,
+                bearer_challenge(
+                    error.oauth_error.unwrap_or_default(),
+                    &error.message,
+                    error.error_uri,
+                ),
+            );
+        }
+        body
+    }
+}
+
+/// `WWW-Authenticate` value for an OAuth 2.0 error (RFC 6750, section 3).
+fn bearer_challenge(error: &str, description: &str, uri: Option<&str>) -> HeaderValue {
+    let mut challenge = format!(
+        "Bearer error=\"{}\", error_description=\"{}\"",
+        quoted(error),
+        quoted(description)
+    );
+    if let Some(uri) = uri {
+        challenge.push_str(&format!(", error_uri=\"{}\"", quoted(uri)));
+    }
+    HeaderValue::from_str(&challenge).expect("Challenge only contains visible ASCII")
+}
+
+/// Drops the characters RFC 6750 does not allow inside challenge attributes.
+fn quoted(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| matches!(c, '\x20'..='\x21' | '\x23'..='\x5B' | '\x5D'..='\x7E'))
+        .collect()
+}
@@ -0,0 +1,32 @@
+use crate::format::ErrorContext;
+
+/// Marks the active OpenTelemetry span as failed by a 5xx error, or a 4xx error when
+/// [`Config::client_span_errors`](crate::Config::client_span_errors) is set, and records the
+/// error as an `exception` event.
+#[cfg(feature = "opentelemetry")]
+pub fn mark_span(enumeration: &'static str, error: &ErrorContext) {
+    use opentelemetry::trace::{get_active_span, Status};
+    use opentelemetry::KeyValue;
+
+    let failed = error.status.is_server_error()
+        || (error.status.is_client_error() && crate::config::client_span_errors());
+    if !failed {
+        return;
+    }
+    get_active_span(|span| {
+        span.add_event(
+            "exception",
+            vec![
+                KeyValue::new(
+                    "exception.type",
+                    format!("{}::{}", enumeration, error.variant),
+                ),
+                KeyValue::new("exception.message", error.message.clone()),
+            ],
+        );
+        span.set_status(Status::error(error.message.clone()));
+    });
+}
+
+#[cfg(not(feature = "opentelemetry"))]
+pub fn mark_span(_enumeration: &'static str, _error: &ErrorContext) {}
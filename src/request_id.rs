@@ -0,0 +1,52 @@
+use axum::http::{HeaderMap, Request};
+use axum::middleware::Next;
+use axum::response::Response;
+
+tokio::task_local! {
+    static REQUEST_ID: Option<String>;
+}
+
+/// Middleware storing the request's ID so derived errors of enums with
+/// `#[error(request_id = "...")]` can send it back.
+///
+/// ```rust
+/// use axum::{middleware, routing::get, Router};
+///
+/// let app: Router = Router::new()
+///     .route("/", get(|| async { "Hello" }))
+///     .layer(middleware::from_fn(axum_error_macro::request_id));
+/// ```
+///
+/// The ID is taken from `x-request-id` when it is at most 128 visible ASCII characters, or else
+/// from the trace ID of a W3C `traceparent`.
+pub async fn request_id<B>(request: Request<B>, next: Next<B>) -> Response {
+    let id = find_request_id(request.headers());
+    REQUEST_ID.scope(id, next.run(request)).await
+}
+
+/// Longest `x-request-id` sent back to clients.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+fn find_request_id(headers: &HeaderMap) -> Option<String> {
+    let id = headers.get("x-request-id").map(|id| id.as_bytes());
+    if let Some(id) = id.filter(|id| is_valid_request_id(id)) {
+        return String::from_utf8(id.to_vec()).ok();
+    }
+    let traceparent = headers.get("traceparent")?.to_str().ok()?;
+    let mut parts = traceparent.trim().split('-');
+    let trace_id = parts.nth(1)?;
+    let valid = trace_id.len() == 32
+        && trace_id.bytes().all(|b| b.is_ascii_hexdigit())
+        && trace_id.bytes().any(|b| b != b'0');
+    valid.then(|| trace_id.to_ascii_lowercase())
+}
+
+/// Whether a client's ID is short visible ASCII, so it is safe to echo in bodies and headers.
+fn is_valid_request_id(id: &[u8]) -> bool {
+    !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN && id.iter().all(u8::is_ascii_graphic)
+}
+
+/// ID of the request being handled, `None` outside of [`request_id`].
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok().flatten()
+}
@@ -0,0 +1,226 @@
+use std::future::Future;
+
+use axum::http::HeaderValue;
+use axum::response::{IntoResponse, Response};
+use serde_json::{json, Value};
+
+use crate::format::{ErrorBody, ErrorContext, ErrorFormatter};
+
+/// Maps an HTTP status code to the closest gRPC status code.
+pub fn grpc_code(status: u16) -> i32 {
+    match status {
+        200..=299 => 0,  // Ok
+        400 => 3,        // InvalidArgument
+        401 => 16,       // Unauthenticated
+        403 => 7,        // PermissionDenied
+        404 => 5,        // NotFound
+        408 | 504 => 4,  // DeadlineExceeded
+        409 => 6,        // AlreadyExists
+        412 => 9,        // FailedPrecondition
+        416 => 11,       // OutOfRange
+        422 => 3,        // InvalidArgument
+        429 => 8,        // ResourceExhausted
+        499 => 1,        // Cancelled
+        501 => 12,       // Unimplemented
+        503 => 14,       // Unavailable
+        500..=599 => 13, // Internal
+        _ => 2,          // Unknown
+    }
+}
+
+/// JSON-RPC error code used when a variant sets no `rpc_code`.
+fn json_rpc_code(status: u16) -> i64 {
+    match status {
+        500..=599 => -32603, // Internal error
+        _ => -32000,         // Server error
+    }
+}
+
+tokio::task_local! {
+    static RPC_ID: Value;
+}
+
+/// Runs a JSON-RPC handler so derived errors it returns carry the request's `id`.
+///
+/// The handler output is converted into a response inside the scope, so errors
+/// returned through `Result` get the id as well.
+///
+/// ```rust
+/// use axum::{response::Response, Json};
+/// use serde_json::Value;
+///
+/// async fn rpc(Json(request): Json<Value>) -> Response {
+///     let id = request["id"].clone();
+///     axum_error_macro::with_rpc_id(id, async move {
+///         // dispatch the call, returning `Result<Json<Value>, Error>`
+///         Ok::<_, std::convert::Infallible>(Json(request))
+///     })
+///     .await
+/// }
+/// ```
+pub async fn with_rpc_id<F, R>(id: Value, handler: F) -> Response
+where
+    F: Future<Output = R>,
+    R: IntoResponse,
+{
+    RPC_ID
+        .scope(id, async move { handler.await.into_response() })
+        .await
+}
+
+/// `id` of the JSON-RPC request being handled, `null` outside of [`with_rpc_id`].
+fn rpc_id() -> Value {
+    RPC_ID.try_with(Value::clone).unwrap_or(Value::Null)
+}
+
+/// "json-rpc": a JSON-RPC 2.0 error object, sent as "application/json".
+pub struct JsonRpc;
+
+impl ErrorFormatter for JsonRpc {
+    fn format(&self, error: &ErrorContext) -> ErrorBody {
+        let code = error
+            .rpc_code
+            .unwrap_or_else(|| json_rpc_code(error.status.as_u16()));
+        let mut object = json!({
+          "code": code,
+          "message": error.message,
+        });
+        if let Some(details) = &error.details {
+            object["data"] = details.clone();
+        }
+        let body = json!({
+          "jsonrpc": "2.0",
+          "error": object,
+          "id": rpc_id(),
+        });
+        ErrorBody::new(
+            HeaderValue::from_static("application/json"),
+            body.to_string(),
+        )
+    }
+}
+
+/// "connect": a Connect protocol error, sent as "application/json".
+pub struct Connect;
+
+impl ErrorFormatter for Connect {
+    fn format(&self, error: &ErrorContext) -> ErrorBody {
+        #[allow(unused_mut)]
+        let mut body = json!({
+          "code": connect_code(error.grpc_code()),
+          "message": error.message,
+        });
+        #[cfg(feature = "prost")]
+        if let Some(details) = &error.details {
+            body["details"] = connect_details(details);
+        }
+        ErrorBody::new(
+            HeaderValue::from_static("application/json"),
+            body.to_string(),
+        )
+    }
+}
+
+/// "application/x-protobuf": a binary `google.rpc.Status`.
+#[cfg(feature = "prost")]
+pub struct Protobuf;
+
+#[cfg(feature = "prost")]
+impl ErrorFormatter for Protobuf {
+    fn format(&self, error: &ErrorContext) -> ErrorBody {
+        ErrorBody::new(
+            HeaderValue::from_static("application/x-protobuf"),
+            encode_status(error.grpc_code(), &error.message, error.details.as_ref()),
+        )
+    }
+}
+
+/// Connect protocol name of a gRPC status code.
+fn connect_code(code: i32) -> &'static str {
+    match code {
+        0 => "ok",
+        1 => "canceled",
+        3 => "invalid_argument",
+        4 => "deadline_exceeded",
+        5 => "not_found",
+        6 => "already_exists",
+        7 => "permission_denied",
+        8 => "resource_exhausted",
+        9 => "failed_precondition",
+        10 => "aborted",
+        11 => "out_of_range",
+        12 => "unimplemented",
+        13 => "internal",
+        14 => "unavailable",
+        15 => "data_loss",
+        16 => "unauthenticated",
+        _ => "unknown",
+    }
+}
+
+/// `google.rpc.Status` message.
+#[cfg(feature = "prost")]
+#[derive(Clone, PartialEq, prost::Message)]
+struct Status {
+    #[prost(int32, tag = "1")]
+    code: i32,
+    #[prost(string, tag = "2")]
+    message: String,
+    #[prost(message, repeated, tag = "3")]
+    details: Vec<prost_types::Any>,
+}
+
+/// Encodes a `google.rpc.Status` carrying `details` as a `google.protobuf.Value`.
+#[cfg(feature = "prost")]
+fn encode_status(code: i32, message: &str, details: Option<&Value>) -> Vec<u8> {
+    use prost::Message;
+
+    Status {
+        code,
+        message: message.to_owned(),
+        details: details
+            .map(|details| prost_types::Any {
+                type_url: "type.googleapis.com/google.protobuf.Value".into(),
+                value: proto_value(details).encode_to_vec(),
+            })
+            .into_iter()
+            .collect(),
+    }
+    .encode_to_vec()
+}
+
+/// Connect error `details` carrying `details` as a `google.protobuf.Value`.
+#[cfg(feature = "prost")]
+fn connect_details(details: &Value) -> Value {
+    use base64::Engine;
+    use prost::Message;
+
+    let value = proto_value(details).encode_to_vec();
+    json!([{
+        "type": "google.protobuf.Value",
+        "value": base64::engine::general_purpose::STANDARD_NO_PAD.encode(value),
+        "debug": details,
+    }])
+}
+
+#[cfg(feature = "prost")]
+fn proto_value(value: &Value) -> prost_types::Value {
+    use prost_types::value::Kind;
+
+    let kind = match value {
+        Value::Null => Kind::NullValue(0),
+        Value::Bool(value) => Kind::BoolValue(*value),
+        Value::Number(value) => Kind::NumberValue(value.as_f64().unwrap_or_default()),
+        Value::String(value) => Kind::StringValue(value.clone()),
+        Value::Array(values) => Kind::ListValue(prost_types::ListValue {
+            values: values.iter().map(proto_value).collect(),
+        }),
+        Value::Object(values) => Kind::StructValue(prost_types::Struct {
+            fields: values
+                .iter()
+                .map(|(name, value)| (name.clone(), proto_value(value)))
+                .collect(),
+        }),
+    };
+    prost_types::Value { kind: Some(kind) }
+}
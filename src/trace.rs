@@ -0,0 +1,53 @@
+use crate::format::ErrorContext;
+
+/// Emits an event for an error turned into a response and records its fields on the
+/// current span, `error_code` only when the variant sets one. The level is ERROR for 5xx,
+/// WARN for 4xx and DEBUG otherwise, unless the variant sets `log = "..."`.
+#[cfg(feature = "tracing")]
+pub fn trace_error(error: &ErrorContext, level: Option<&str>) {
+    use tracing::Level;
+
+    let level = match level {
+        Some("trace") => Level::TRACE,
+        Some("debug") => Level::DEBUG,
+        Some("info") => Level::INFO,
+        Some("warn") => Level::WARN,
+        Some(_) => Level::ERROR,
+        None if error.status.is_server_error() => Level::ERROR,
+        None if error.status.is_client_error() => Level::WARN,
+        None => Level::DEBUG,
+    };
+    let status = error.status.as_u16();
+    let internal = error.internal.as_deref();
+    let error_code = error.error_code;
+    macro_rules! event {
+        ($level:expr) => {
+            tracing::event!(
+                $level,
+                status,
+                variant = error.variant,
+                error_code,
+                message = %error.message,
+                internal,
+            )
+        };
+    }
+    match level {
+        Level::TRACE => event!(Level::TRACE),
+        Level::DEBUG => event!(Level::DEBUG),
+        Level::INFO => event!(Level::INFO),
+        Level::WARN => event!(Level::WARN),
+        _ => event!(Level::ERROR),
+    }
+
+    let span = tracing::Span::current();
+    span.record("status", status);
+    span.record("variant", error.variant);
+    if let Some(error_code) = error_code {
+        span.record("error_code", error_code);
+    }
+    span.record("message", error.message.as_str());
+}
+
+#[cfg(not(feature = "tracing"))]
+pub fn trace_error(_error: &ErrorContext, _level: Option<&str>) {}
@@ -0,0 +1,79 @@
+mod backtrace {
+    use std::backtrace::Backtrace;
+
+    use axum_error_macro::ErrorResponse;
+    use hyper::body::HttpBody;
+    use serde_json::Value;
+
+    #[derive(ErrorResponse)]
+    #[error_format("application/json")]
+    enum Error {
+        #[error(code = 500, msg = "Internal error", internal = "db failure: {0}")]
+        DatabaseFailure(String, #[error(backtrace)] Backtrace),
+
+        #[error(code = 404, msg = "User was not found")]
+        UserNotFound,
+    }
+
+    #[tokio::test]
+    async fn backtrace_constructor() {
+        let error = Error::database_failure("connection reset".into());
+        assert!(
+            matches!(error, Error::DatabaseFailure(ref reason, _) if reason == "connection reset")
+        );
+
+        let response = error.into_response();
+        assert_eq!(response.status(), 500);
+        let body = response.into_body().data().await.unwrap().unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["message"], "Internal error");
+    }
+
+    #[cfg(not(feature = "debug-details"))]
+    #[tokio::test]
+    async fn hidden_backtrace() {
+        let error = Error::DatabaseFailure("connection reset".into(), Backtrace::force_capture());
+        let body = error
+            .into_response()
+            .into_body()
+            .data()
+            .await
+            .unwrap()
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert!(body.get("backtrace").is_none());
+    }
+
+    #[cfg(feature = "debug-details")]
+    #[tokio::test]
+    async fn debug_backtrace() {
+        let error = Error::DatabaseFailure("connection reset".into(), Backtrace::force_capture());
+        let body = error
+            .into_response()
+            .into_body()
+            .data()
+            .await
+            .unwrap()
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert!(body["backtrace"]
+            .as_str()
+            .unwrap()
+            .contains("debug_backtrace"));
+    }
+
+    #[tokio::test]
+    async fn without_backtrace() {
+        assert_eq!(Error::UserNotFound.into_response().status(), 404);
+    }
+
+    #[test]
+    fn internal_message() {
+        let error = Error::DatabaseFailure("connection reset".into(), Backtrace::force_capture());
+        assert_eq!(
+            error.internal_message(),
+            Some("db failure: connection reset".into())
+        );
+        assert_eq!(Error::UserNotFound.internal_message(), None);
+    }
+}
@@ -0,0 +1,57 @@
+#![cfg(any(feature = "msgpack", feature = "cbor"))]
+
+mod binary {
+    use axum_error_macro::ErrorResponse;
+    use hyper::body::HttpBody;
+    use serde_json::{json, Value};
+
+    #[cfg(feature = "msgpack")]
+    #[tokio::test]
+    async fn right_transferred_data_msgpack() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/msgpack")]
+        enum Error {
+            #[error(code = 404, msg = "User by {} id was not found")]
+            UserByIdNotFound(u32),
+        }
+
+        let mut response = Error::UserByIdNotFound(12).into_response();
+        let body = response.data().await.unwrap().unwrap().to_vec();
+
+        assert_eq!(
+            response.headers().get("Content-Type").unwrap(),
+            "application/msgpack"
+        );
+        assert_eq!(
+            rmp_serde::from_slice::<Value>(&body).unwrap(),
+            json!({ "message": "User by 12 id was not found" })
+        );
+    }
+
+    #[cfg(feature = "cbor")]
+    #[tokio::test]
+    async fn right_transferred_data_cbor() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/cbor")]
+        enum Error {
+            #[error(code = 422, msg = "Validation failed", error_code = "INVALID_FIELDS")]
+            InvalidFields(#[error(details)] Vec<String>),
+        }
+
+        let mut response = Error::InvalidFields(vec!["email".into()]).into_response();
+        let body = response.data().await.unwrap().unwrap().to_vec();
+
+        assert_eq!(
+            response.headers().get("Content-Type").unwrap(),
+            "application/cbor"
+        );
+        assert_eq!(
+            ciborium::from_reader::<Value, _>(body.as_slice()).unwrap(),
+            json!({
+                "message": "Validation failed",
+                "code": "INVALID_FIELDS",
+                "details": ["email"],
+            })
+        );
+    }
+}
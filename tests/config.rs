@@ -0,0 +1,50 @@
+// Not part of tests/mod.rs: the configuration is process-wide and would leak into other tests.
+mod config {
+    use axum_error_macro::{Config, ErrorResponse};
+    use hyper::body::HttpBody;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn configured_format() {
+        #[derive(ErrorResponse)]
+        enum Error {
+            #[error(code = 422, msg = "Validation failed")]
+            InvalidFields(#[error(details)] Vec<String>),
+        }
+
+        axum_error_macro::configure(
+            Config::default()
+                .format("application/json")
+                .include_details(false),
+        );
+
+        let response = Error::InvalidFields(vec!["email".into()]).into_response();
+        assert_eq!(response.headers()["Content-Type"], "application/json");
+        assert_eq!(
+            response.into_body().data().await.unwrap().unwrap().to_vec(),
+            json!({ "message": "Validation failed" })
+                .to_string()
+                .as_bytes()
+        );
+
+        {
+            #[derive(ErrorResponse)]
+            #[error_format("text/plain")]
+            enum PinnedError {
+                #[error(code = 404, msg = "User was not found")]
+                UserNotFound,
+            }
+
+            assert_eq!(
+                PinnedError::UserNotFound.into_response().headers()["Content-Type"],
+                "text/plain; charset=utf-8"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "can not be configured")]
+    fn unavailable_format() {
+        axum_error_macro::configure(Config::default().format("oauth2"));
+    }
+}
@@ -0,0 +1,133 @@
+mod connect {
+    use axum_error_macro::ErrorResponse;
+    use hyper::body::HttpBody;
+    use serde_json::json;
+
+    #[derive(ErrorResponse)]
+    #[error_format("connect")]
+    enum Error {
+        #[error(code = 404, msg = "User by {} id was not found")]
+        UserByIdNotFound(u32),
+        #[error(code = 400, msg = "Account {} is locked", grpc = "FailedPrecondition")]
+        AccountLocked(u32),
+    }
+
+    #[tokio::test]
+    async fn right_transferred_data_connect() {
+        let mut response = Error::UserByIdNotFound(12).into_response();
+        let error_msg = json!({
+            "code": "not_found",
+            "message": "User by 12 id was not found",
+        });
+
+        assert_eq!(
+            response.headers().get("Content-Type").unwrap(),
+            "application/json"
+        );
+        assert_eq!(
+            response.data().await.unwrap().unwrap().to_vec(),
+            error_msg.to_string().as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn overridden_code_connect() {
+        let error_msg = json!({
+            "code": "failed_precondition",
+            "message": "Account 7 is locked",
+        });
+
+        assert_eq!(
+            Error::AccountLocked(7)
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            error_msg.to_string().as_bytes()
+        );
+    }
+
+    #[cfg(feature = "prost")]
+    #[tokio::test]
+    async fn details_connect() {
+        use base64::Engine;
+        use prost::Message;
+
+        #[derive(ErrorResponse)]
+        #[error_format("connect")]
+        enum Error {
+            #[error(code = 422, msg = "Validation failed")]
+            InvalidFields(#[error(details)] Vec<String>),
+        }
+
+        let body = Error::InvalidFields(vec!["email".into()])
+            .into_response()
+            .data()
+            .await
+            .unwrap()
+            .unwrap()
+            .to_vec();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let detail = &body["details"][0];
+        let value = base64::engine::general_purpose::STANDARD_NO_PAD
+            .decode(detail["value"].as_str().unwrap())
+            .unwrap();
+
+        assert_eq!(body["code"], "invalid_argument");
+        assert_eq!(detail["type"], "google.protobuf.Value");
+        assert_eq!(detail["debug"], json!(["email"]));
+        assert_eq!(
+            prost_types::Value::decode(value.as_slice()).unwrap(),
+            prost_types::Value {
+                kind: Some(prost_types::value::Kind::ListValue(
+                    prost_types::ListValue {
+                        values: vec![prost_types::Value {
+                            kind: Some(prost_types::value::Kind::StringValue("email".into())),
+                        }],
+                    }
+                )),
+            }
+        );
+    }
+
+    #[cfg(feature = "prost")]
+    #[tokio::test]
+    async fn binary_status() {
+        use prost::Message;
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        struct Status {
+            #[prost(int32, tag = "1")]
+            code: i32,
+            #[prost(string, tag = "2")]
+            message: String,
+            #[prost(message, repeated, tag = "3")]
+            details: Vec<prost_types::Any>,
+        }
+
+        #[derive(ErrorResponse)]
+        #[error_format("application/x-protobuf")]
+        enum Error {
+            #[error(code = 404, msg = "User by {} id was not found")]
+            UserByIdNotFound(u32),
+        }
+
+        let mut response = Error::UserByIdNotFound(12).into_response();
+        let body = response.data().await.unwrap().unwrap().to_vec();
+
+        assert_eq!(
+            response.headers().get("Content-Type").unwrap(),
+            "application/x-protobuf"
+        );
+        assert_eq!(
+            Status::decode(body.as_slice()).unwrap(),
+            Status {
+                code: 5,
+                message: "User by 12 id was not found".into(),
+                details: vec![],
+            }
+        );
+    }
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "metrics")]
+
+mod counter {
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use axum_error_macro::ErrorResponse;
+    use metrics::{
+        Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+
+    #[derive(Default)]
+    struct Counters(Mutex<BTreeMap<String, Arc<AtomicU64>>>);
+
+    struct Count(Arc<AtomicU64>);
+
+    impl CounterFn for Count {
+        fn increment(&self, value: u64) {
+            self.0.fetch_add(value, Ordering::Relaxed);
+        }
+
+        fn absolute(&self, value: u64) {
+            self.0.fetch_max(value, Ordering::Relaxed);
+        }
+    }
+
+    impl Recorder for Counters {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            let labels: Vec<String> = key
+                .labels()
+                .map(|label| format!("{}={}", label.key(), label.value()))
+                .collect();
+            let name = format!("{}{{{}}}", key.name(), labels.join(","));
+            let count = self.0.lock().unwrap().entry(name).or_default().clone();
+            Counter::from_arc(Arc::new(Count(count)))
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    impl Counters {
+        fn get(&self, name: &str) -> u64 {
+            self.0
+                .lock()
+                .unwrap()
+                .get(name)
+                .map_or(0, |count| count.load(Ordering::Relaxed))
+        }
+    }
+
+    #[test]
+    fn counted_errors() {
+        #[derive(ErrorResponse)]
+        enum Error {
+            #[error(code = 404, msg = "User by {} id was not found")]
+            UserByIdNotFound(u32),
+        }
+
+        let counters = Counters::default();
+        metrics::with_local_recorder(&counters, || {
+            Error::UserByIdNotFound(1).into_response();
+            Error::UserByIdNotFound(2).into_response();
+        });
+
+        assert_eq!(
+            counters.get("http_errors_total{enum=Error,variant=UserByIdNotFound,status=404}"),
+            2
+        );
+    }
+
+    #[test]
+    fn metric_name() {
+        #[derive(ErrorResponse)]
+        #[error(metric = "user_errors_total")]
+        enum UserError {
+            #[error(code = 500, msg = "Internal error")]
+            Internal,
+        }
+
+        let counters = Counters::default();
+        metrics::with_local_recorder(&counters, || {
+            UserError::Internal.into_response();
+        });
+
+        assert_eq!(
+            counters.get("user_errors_total{enum=UserError,variant=Internal,status=500}"),
+            1
+        );
+    }
+}
@@ -0,0 +1,97 @@
+mod details {
+    use axum_error_macro::ErrorResponse;
+    use hyper::body::HttpBody;
+    use serde::Serialize;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    struct FieldError {
+        field: String,
+        reason: String,
+    }
+
+    #[tokio::test]
+    async fn details_json() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/json")]
+        enum Error {
+            #[error(code = 422, msg = "Validation failed")]
+            InvalidFields(#[error(details)] Vec<FieldError>),
+        }
+        let fields = vec![FieldError {
+            field: "email".into(),
+            reason: "must not be empty".into(),
+        }];
+
+        let error_msg = json!({
+            "message": "Validation failed",
+            "details": [{ "field": "email", "reason": "must not be empty" }],
+        });
+
+        assert_eq!(
+            Error::InvalidFields(fields)
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            error_msg.to_string().as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn details_with_params_json() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/json")]
+        enum Error {
+            #[error(code = 422, msg = "User {} has {} invalid fields")]
+            InvalidUserFields(String, usize, #[error(details)] Vec<FieldError>),
+        }
+        let fields = vec![FieldError {
+            field: "email".into(),
+            reason: "must not be empty".into(),
+        }];
+
+        let error_msg = json!({
+            "message": "User Bebra has 1 invalid fields",
+            "details": [{ "field": "email", "reason": "must not be empty" }],
+        });
+
+        assert_eq!(
+            Error::InvalidUserFields("Bebra".into(), fields.len(), fields)
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            error_msg.to_string().as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn details_text() {
+        #[derive(ErrorResponse)]
+        #[error_format("text/plain")]
+        enum Error {
+            #[error(code = 422, msg = "Validation failed")]
+            InvalidFields(#[error(details)] Vec<FieldError>),
+        }
+        let fields = vec![FieldError {
+            field: "email".into(),
+            reason: "must not be empty".into(),
+        }];
+
+        assert_eq!(
+            Error::InvalidFields(fields)
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            "Validation failed".as_bytes()
+        );
+    }
+}
@@ -0,0 +1,50 @@
+mod extension {
+    use std::sync::Arc;
+
+    use axum::http::StatusCode;
+    use axum_error_macro::{ErrorRecord, ErrorResponse};
+
+    #[test]
+    fn record() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/json")]
+        enum Error {
+            #[error(code = 404, msg = "User by {} id was not found")]
+            UserByIdNotFound(u32),
+        }
+
+        let response = Error::UserByIdNotFound(12).into_response();
+
+        assert_eq!(
+            response.extensions().get::<ErrorRecord>(),
+            Some(&ErrorRecord {
+                error: "Error",
+                variant: "UserByIdNotFound",
+                error_code: None,
+                status: StatusCode::NOT_FOUND,
+            })
+        );
+        assert!(response.extensions().get::<Arc<Error>>().is_none());
+    }
+
+    #[test]
+    fn attach() {
+        #[derive(ErrorResponse, Debug, PartialEq)]
+        #[error_format("application/json")]
+        #[error(attach)]
+        enum Error {
+            #[error(code = 409, msg = "User {} already exists", error_code = "USER_EXISTS")]
+            UserExists(String),
+        }
+
+        let response = Error::UserExists("alice".into()).into_response();
+
+        assert_eq!(
+            response.extensions().get::<Arc<Error>>().map(Arc::as_ref),
+            Some(&Error::UserExists("alice".into()))
+        );
+        let record = response.extensions().get::<ErrorRecord>().unwrap();
+        assert_eq!(record.status, StatusCode::CONFLICT);
+        assert_eq!(record.error_code, Some("USER_EXISTS"));
+    }
+}
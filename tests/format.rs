@@ -47,7 +47,7 @@ mod format {
                 .headers()
                 .get("Content-Type")
                 .unwrap(),
-            "text/plain"
+            "text/plain; charset=utf-8"
         );
         assert_eq!(
             Error::BadRequest
@@ -55,7 +55,7 @@ mod format {
                 .headers()
                 .get("Content-Type")
                 .unwrap(),
-            "text/plain"
+            "text/plain; charset=utf-8"
         );
     }
 
@@ -75,7 +75,7 @@ mod format {
                 .headers()
                 .get("Content-Type")
                 .unwrap(),
-            "text/plain"
+            "text/plain; charset=utf-8"
         );
         assert_eq!(
             Error::BadRequest
@@ -83,7 +83,65 @@ mod format {
                 .headers()
                 .get("Content-Type")
                 .unwrap(),
-            "text/plain"
+            "text/plain; charset=utf-8"
+        );
+    }
+
+    #[test]
+    fn html_format() {
+        #[derive(ErrorResponse)]
+        #[error_format("text/html")]
+        enum Error {
+            #[error(code = 500, msg = "123")]
+            InternalServerError,
+            #[error(code = 400, msg = "123")]
+            BadRequest,
+        }
+
+        assert_eq!(
+            Error::InternalServerError
+                .into_response()
+                .headers()
+                .get("Content-Type")
+                .unwrap(),
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(
+            Error::BadRequest
+                .into_response()
+                .headers()
+                .get("Content-Type")
+                .unwrap(),
+            "text/html; charset=utf-8"
+        );
+    }
+
+    #[test]
+    fn variant_format() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/json")]
+        enum Error {
+            #[error(code = 500, msg = "123")]
+            InternalServerError,
+            #[error(code = 503, msg = "123", format = "text/plain")]
+            HealthCheckFailed,
+        }
+
+        assert_eq!(
+            Error::InternalServerError
+                .into_response()
+                .headers()
+                .get("Content-Type")
+                .unwrap(),
+            "application/json"
+        );
+        assert_eq!(
+            Error::HealthCheckFailed
+                .into_response()
+                .headers()
+                .get("Content-Type")
+                .unwrap(),
+            "text/plain; charset=utf-8"
         );
     }
 }
@@ -0,0 +1,108 @@
+mod formatter {
+    use axum::http::{HeaderValue, StatusCode};
+    use axum_error_macro::{formats, ErrorBody, ErrorContext, ErrorFormatter, ErrorResponse};
+    use hyper::body::HttpBody;
+    use serde_json::json;
+
+    fn render(error: &ErrorContext) -> ErrorBody {
+        let body = json!({
+            "error": error.variant,
+            "status": error.status.as_u16(),
+            "message": error.message,
+        });
+        ErrorBody::new(
+            HeaderValue::from_static("application/json"),
+            body.to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn function_formatter() {
+        #[derive(ErrorResponse)]
+        #[error_format(with = render)]
+        enum Error {
+            #[error(code = 404, msg = "User by {} id was not found")]
+            UserByIdNotFound(u32),
+        }
+
+        let response = Error::UserByIdNotFound(12).into_response();
+        assert_eq!(response.headers()["Content-Type"], "application/json");
+        assert_eq!(
+            response.into_body().data().await.unwrap().unwrap().to_vec(),
+            json!({
+                "error": "UserByIdNotFound",
+                "status": 404,
+                "message": "User by 12 id was not found",
+            })
+            .to_string()
+            .as_bytes()
+        );
+    }
+
+    struct Tagged(&'static str);
+
+    impl ErrorFormatter for Tagged {
+        fn format(&self, error: &ErrorContext) -> ErrorBody {
+            let mut body = formats::Json.format(error);
+            body.headers
+                .insert("X-Error-Tag", HeaderValue::from_static(self.0));
+            body
+        }
+    }
+
+    #[tokio::test]
+    async fn wrapped_formatter() {
+        #[derive(ErrorResponse)]
+        #[error_format(with = Tagged("users"))]
+        enum Error {
+            #[error(code = 400, msg = "Bad Request!!!")]
+            BadRequest,
+        }
+
+        let response = Error::BadRequest.into_response();
+        assert_eq!(response.headers()["Content-Type"], "application/json");
+        assert_eq!(response.headers()["X-Error-Tag"], "users");
+        assert_eq!(
+            response.into_body().data().await.unwrap().unwrap().to_vec(),
+            json!({ "message": "Bad Request!!!" })
+                .to_string()
+                .as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn variant_overrides_formatter() {
+        #[derive(ErrorResponse)]
+        #[error_format(with = render)]
+        enum Error {
+            #[error(code = 503, msg = "Unhealthy", format = "text/plain")]
+            Unhealthy,
+        }
+
+        let response = Error::Unhealthy.into_response();
+        assert_eq!(
+            response.headers()["Content-Type"],
+            "text/plain; charset=utf-8"
+        );
+        assert_eq!(
+            response.into_body().data().await.unwrap().unwrap().to_vec(),
+            "Unhealthy".as_bytes()
+        );
+    }
+
+    #[test]
+    fn built_context() {
+        let mut error =
+            ErrorContext::new(StatusCode::NOT_FOUND, "UserNotFound", "User was not found");
+        error.details = Some(json!({ "id": 12 }));
+
+        let body = formats::Json.format(&error);
+        assert_eq!(body.content_type, "application/json");
+        assert_eq!(
+            body.bytes,
+            json!({ "message": "User was not found", "details": { "id": 12 } })
+                .to_string()
+                .as_bytes()
+        );
+    }
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "async-graphql")]
+
+mod graphql {
+    use async_graphql::{Error as GraphQLError, ErrorExtensions, Value};
+    use axum_error_macro::ErrorResponse;
+
+    #[derive(ErrorResponse)]
+    #[error_format("application/json")]
+    enum Error {
+        #[error(code = 404, msg = "User by {} id was not found")]
+        UserByIdNotFound(u32),
+        #[error(code = 409, msg = "User already exists", error_code = "USER_EXISTS")]
+        UserExists,
+    }
+
+    #[test]
+    fn graphql_error() {
+        let error = GraphQLError::from(Error::UserByIdNotFound(12));
+        let extensions = error.extensions.unwrap();
+
+        assert_eq!(error.message, "User by 12 id was not found");
+        assert_eq!(extensions.get("status"), Some(&Value::from(404)));
+        assert_eq!(
+            extensions.get("variant"),
+            Some(&Value::from("UserByIdNotFound"))
+        );
+        assert_eq!(extensions.get("code"), None);
+    }
+
+    #[test]
+    fn graphql_error_code() {
+        let error = GraphQLError::from(Error::UserExists);
+        let extensions = error.extensions.unwrap();
+
+        assert_eq!(extensions.get("code"), Some(&Value::from("USER_EXISTS")));
+        assert_eq!(extensions.get("status"), Some(&Value::from(409)));
+    }
+
+    #[test]
+    fn graphql_error_extensions() {
+        let error = Error::UserByIdNotFound(12)
+            .extend()
+            .extend_with(|_, extensions| extensions.set("retry", false));
+        let extensions = error.extensions.unwrap();
+
+        assert_eq!(extensions.get("status"), Some(&Value::from(404)));
+        assert_eq!(extensions.get("retry"), Some(&Value::from(false)));
+    }
+}
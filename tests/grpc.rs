@@ -0,0 +1,45 @@
+#![cfg(feature = "tonic")]
+
+mod grpc {
+    use axum_error_macro::ErrorResponse;
+    use tonic::{Code, Status};
+
+    #[derive(ErrorResponse)]
+    #[error_format("application/json")]
+    enum Error {
+        #[error(code = 500, msg = "Internal server error!!!")]
+        Internal,
+        #[error(code = 404, msg = "User by {} id was not found")]
+        UserByIdNotFound(u32),
+        #[error(code = 409, msg = "User {} already exists")]
+        UserExists(String),
+        #[error(code = 401, msg = "Unauthorized", auth_challenge = "Bearer")]
+        Unauthorized,
+        #[error(code = 400, msg = "Account {} is locked", grpc = "FailedPrecondition")]
+        AccountLocked(u32),
+    }
+
+    #[test]
+    fn default_grpc_codes() {
+        let status = Status::from(Error::UserByIdNotFound(12));
+        assert_eq!(status.code(), Code::NotFound);
+        assert_eq!(status.message(), "User by 12 id was not found");
+
+        assert_eq!(
+            Status::from(Error::UserExists("Bebra".into())).code(),
+            Code::AlreadyExists
+        );
+        assert_eq!(
+            Status::from(Error::Unauthorized).code(),
+            Code::Unauthenticated
+        );
+        assert_eq!(Status::from(Error::Internal).code(), Code::Internal);
+    }
+
+    #[test]
+    fn overridden_grpc_code() {
+        let status = Status::from(Error::AccountLocked(7));
+        assert_eq!(status.code(), Code::FailedPrecondition);
+        assert_eq!(status.message(), "Account 7 is locked");
+    }
+}
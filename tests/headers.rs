@@ -0,0 +1,151 @@
+mod headers {
+    use std::time::Duration;
+
+    use axum::http::Method;
+    use axum_error_macro::ErrorResponse;
+    use hyper::body::HttpBody;
+
+    #[test]
+    fn static_headers() {
+        #[derive(ErrorResponse)]
+        enum Error {
+            #[error(
+                code = 429,
+                msg = "Too many requests",
+                headers("Retry-After" = "30", "X-RateLimit-Remaining" = "0")
+            )]
+            TooManyRequests,
+        }
+
+        let response = Error::TooManyRequests.into_response();
+        assert_eq!(response.headers()["Retry-After"], "30");
+        assert_eq!(response.headers()["X-RateLimit-Remaining"], "0");
+        assert_eq!(
+            response.headers()["Content-Type"],
+            "text/plain; charset=utf-8"
+        );
+    }
+
+    #[tokio::test]
+    async fn field_headers() {
+        #[derive(ErrorResponse)]
+        enum Error {
+            #[error(code = 429, msg = "Too many requests", header("Retry-After") = 0)]
+            TooManyRequests(Duration),
+
+            #[error(
+                code = 503,
+                msg = "{} is unavailable",
+                header("Retry-After") = 1,
+                header("X-Region") = 2
+            )]
+            Unavailable(String, u32, &'static str),
+        }
+
+        let response = Error::TooManyRequests(Duration::from_millis(30_500)).into_response();
+        assert_eq!(response.headers()["Retry-After"], "30");
+        assert_eq!(
+            response.into_body().data().await.unwrap().unwrap().to_vec(),
+            "Too many requests".as_bytes()
+        );
+
+        let response = Error::Unavailable("Search".into(), 120, "eu-west").into_response();
+        assert_eq!(response.headers()["Retry-After"], "120");
+        assert_eq!(response.headers()["X-Region"], "eu-west");
+        assert_eq!(
+            response.into_body().data().await.unwrap().unwrap().to_vec(),
+            "Search is unavailable".as_bytes()
+        );
+    }
+
+    #[test]
+    fn invalid_field_header() {
+        #[derive(ErrorResponse)]
+        enum Error {
+            #[error(code = 400, msg = "Bad Request!!!", header("X-Reason") = 0)]
+            BadRequest(String),
+        }
+
+        let response = Error::BadRequest("line\nbreak".into()).into_response();
+        assert_eq!(response.status(), 400);
+        assert!(response.headers().get("X-Reason").is_none());
+    }
+
+    #[test]
+    fn auth_challenge() {
+        #[derive(ErrorResponse)]
+        enum Error {
+            #[error(
+                code = 401,
+                msg = "Unauthorized",
+                auth_challenge = "Bearer realm=\"api\""
+            )]
+            Unauthorized,
+        }
+
+        let response = Error::Unauthorized.into_response();
+        assert_eq!(
+            response.headers()["WWW-Authenticate"],
+            "Bearer realm=\"api\""
+        );
+    }
+
+    #[test]
+    fn allow() {
+        #[derive(ErrorResponse)]
+        enum Error {
+            #[error(code = 405, msg = "Method not allowed", allow = "GET, POST")]
+            ReadOnly,
+
+            #[error(code = 405, msg = "{} only accepts some methods", allow = 1)]
+            MethodNotAllowed(&'static str, Vec<Method>),
+        }
+
+        assert_eq!(
+            Error::ReadOnly.into_response().headers()["Allow"],
+            "GET, POST"
+        );
+        assert_eq!(
+            Error::MethodNotAllowed("/users", vec![Method::GET, Method::DELETE])
+                .into_response()
+                .headers()["Allow"],
+            "GET, DELETE"
+        );
+    }
+
+    #[test]
+    fn default_cache() {
+        #[derive(ErrorResponse)]
+        enum Error {
+            #[error(code = 404, msg = "User was not found")]
+            UserNotFound,
+        }
+
+        assert_eq!(
+            Error::UserNotFound.into_response().headers()["Cache-Control"],
+            "no-store"
+        );
+    }
+
+    #[test]
+    fn cache_policy() {
+        #[derive(ErrorResponse)]
+        #[error(cache = "no-cache")]
+        enum Error {
+            #[error(code = 500, msg = "Internal server error")]
+            Internal,
+
+            #[error(code = 404, msg = "Page was not found", cache = "public, max-age=60")]
+            PageNotFound,
+        }
+
+        assert_eq!(
+            Error::Internal.into_response().headers()["Cache-Control"],
+            "no-cache"
+        );
+        assert_eq!(
+            Error::PageNotFound.into_response().headers()["Cache-Control"],
+            "public, max-age=60"
+        );
+    }
+}
@@ -0,0 +1,99 @@
+mod html {
+    use axum::http::StatusCode;
+    use axum_error_macro::ErrorResponse;
+    use hyper::body::HttpBody;
+
+    #[tokio::test]
+    async fn right_page_html() {
+        #[derive(ErrorResponse)]
+        #[error_format("text/html")]
+        enum Error {
+            #[error(code = 404, msg = "User by {} id was not found")]
+            UserByIdNotFound(u32),
+        }
+
+        let page = "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>404 Not Found</title></head>\n<body>\n<h1>404 Not Found</h1>\n<p>User by 12 id was not found</p>\n</body>\n</html>\n";
+
+        assert_eq!(
+            Error::UserByIdNotFound(12)
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            page.as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn escaped_param_html() {
+        #[derive(ErrorResponse)]
+        #[error_format("text/html")]
+        enum Error {
+            #[error(code = 404, msg = "User {} was not found")]
+            UserNotFound(String),
+        }
+
+        let body = Error::UserNotFound("<script>alert('&')</script>".into())
+            .into_response()
+            .data()
+            .await
+            .unwrap()
+            .unwrap()
+            .to_vec();
+        let body = String::from_utf8(body).unwrap();
+
+        assert!(body.contains(
+            "<p>User &lt;script&gt;alert(&#x27;&amp;&#x27;)&lt;/script&gt; was not found</p>"
+        ));
+        assert!(!body.contains("<script>"));
+    }
+
+    fn render(code: StatusCode, msg: &str) -> String {
+        format!("<main data-code=\"{}\">{}</main>", code.as_u16(), msg)
+    }
+
+    #[tokio::test]
+    async fn template_html() {
+        #[derive(ErrorResponse)]
+        #[error_format("text/html", template = render)]
+        enum Error {
+            #[error(code = 400, msg = "Bad Request!!!")]
+            BadRequest,
+        }
+
+        assert_eq!(
+            Error::BadRequest
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            "<main data-code=\"400\">Bad Request!!!</main>".as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn escaped_param_template_html() {
+        #[derive(ErrorResponse)]
+        #[error_format("text/html", template = render)]
+        enum Error {
+            #[error(code = 404, msg = "User {} was not found")]
+            UserNotFound(String),
+        }
+
+        assert_eq!(
+            Error::UserNotFound("<script>alert('&')</script>".into())
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            "<main data-code=\"404\">User &lt;script&gt;alert(&#x27;&amp;&#x27;)&lt;/script&gt; was not found</main>"
+                .as_bytes()
+        );
+    }
+}
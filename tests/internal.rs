@@ -0,0 +1,127 @@
+mod internal {
+    use axum_error_macro::ErrorResponse;
+    use hyper::body::HttpBody;
+    use serde_json::json;
+
+    #[derive(Debug)]
+    struct DbError(&'static str);
+
+    #[derive(ErrorResponse)]
+    #[error_format("application/json")]
+    enum Error {
+        #[error(code = 500, msg = "Internal error", internal = "db failure: {0:?}")]
+        Database(DbError),
+
+        #[error(
+            code = 404,
+            msg = "User {} was not found",
+            internal = "lookup of {} in {1}"
+        )]
+        UserNotFound(String, &'static str),
+
+        #[error(code = 400, msg = "Bad Request!!!")]
+        BadRequest,
+    }
+
+    #[test]
+    fn internal_message() {
+        assert_eq!(
+            Error::Database(DbError("connection reset")).internal_message(),
+            Some("db failure: DbError(\"connection reset\")".into())
+        );
+        assert_eq!(
+            Error::UserNotFound("Bebra".into(), "users").internal_message(),
+            Some("lookup of Bebra in users".into())
+        );
+        assert_eq!(Error::BadRequest.internal_message(), None);
+    }
+
+    #[tokio::test]
+    async fn details_before_formatted_field() {
+        #[derive(ErrorResponse)]
+        #[error_format("text/plain")]
+        enum Error {
+            #[error(
+                code = 422,
+                msg = "Request {1} is invalid",
+                internal = "request {1} has invalid {0:?}"
+            )]
+            InvalidRequest(#[error(details)] Vec<&'static str>, u32),
+        }
+
+        assert_eq!(
+            Error::InvalidRequest(vec!["email"], 7).internal_message(),
+            Some("request 7 has invalid [\"email\"]".into())
+        );
+        assert!(String::from_utf8(
+            Error::InvalidRequest(vec!["email"], 7)
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec()
+        )
+        .unwrap()
+        .starts_with("Request 7 is invalid"));
+    }
+
+    #[tokio::test]
+    async fn header_field_before_indexed_field() {
+        #[derive(ErrorResponse)]
+        #[error_format("text/plain")]
+        enum Error {
+            #[error(
+                code = 415,
+                msg = "{2} is not supported, use {1}",
+                header("Accept-Post") = 0
+            )]
+            UnsupportedMediaType(&'static str, &'static str, String),
+        }
+
+        let response = Error::UnsupportedMediaType("text/csv", "CSV", "XML".into()).into_response();
+        assert_eq!(response.headers()["Accept-Post"], "text/csv");
+        assert!(
+            String::from_utf8(response.into_body().data().await.unwrap().unwrap().to_vec())
+                .unwrap()
+                .starts_with("XML is not supported, use CSV")
+        );
+    }
+
+    #[cfg(not(feature = "debug-details"))]
+    #[tokio::test]
+    async fn hidden_internal_message() {
+        assert_eq!(
+            Error::Database(DbError("connection reset"))
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            json!({ "message": "Internal error" })
+                .to_string()
+                .as_bytes()
+        );
+    }
+
+    #[cfg(feature = "debug-details")]
+    #[tokio::test]
+    async fn debug_internal_message() {
+        assert_eq!(
+            Error::Database(DbError("connection reset"))
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            json!({
+                "message": "Internal error",
+                "internal": "db failure: DbError(\"connection reset\")",
+            })
+            .to_string()
+            .as_bytes()
+        );
+    }
+}
@@ -0,0 +1,93 @@
+mod json_api {
+    use axum_error_macro::ErrorResponse;
+    use hyper::body::HttpBody;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn right_transferred_data_json_api() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/vnd.api+json")]
+        enum Error {
+            #[error(code = 404, msg = "User by {} id was not found")]
+            UserByIdNotFound(u32),
+        }
+
+        let mut response = Error::UserByIdNotFound(12).into_response();
+        let error_msg = json!({
+            "errors": [{
+                "status": "404",
+                "title": "Not Found",
+                "detail": "User by 12 id was not found",
+            }]
+        });
+
+        assert_eq!(
+            response.headers().get("Content-Type").unwrap(),
+            "application/vnd.api+json"
+        );
+        assert_eq!(
+            response.data().await.unwrap().unwrap().to_vec(),
+            error_msg.to_string().as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn title_and_pointer_json_api() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/vnd.api+json")]
+        enum Error {
+            #[error(code = 422, msg = "{} must not be empty", title = "Invalid Attribute")]
+            EmptyAttribute(String, #[error(pointer)] &'static str),
+        }
+
+        let error_msg = json!({
+            "errors": [{
+                "status": "422",
+                "title": "Invalid Attribute",
+                "detail": "email must not be empty",
+                "source": { "pointer": "/data/attributes/email" },
+            }]
+        });
+
+        assert_eq!(
+            Error::EmptyAttribute("email".into(), "/data/attributes/email")
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            error_msg.to_string().as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn error_code_json_api() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/vnd.api+json")]
+        enum Error {
+            #[error(code = 409, msg = "User already exists", error_code = "USER_EXISTS")]
+            UserExists,
+        }
+
+        let error_msg = json!({
+            "errors": [{
+                "status": "409",
+                "code": "USER_EXISTS",
+                "title": "Conflict",
+                "detail": "User already exists",
+            }]
+        });
+
+        assert_eq!(
+            Error::UserExists
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            error_msg.to_string().as_bytes()
+        );
+    }
+}
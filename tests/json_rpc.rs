@@ -0,0 +1,94 @@
+mod json_rpc {
+    use axum::body::Body;
+    use axum::http::Request;
+    use axum::response::Response;
+    use axum::{routing::post, Json, Router};
+    use axum_error_macro::ErrorResponse;
+    use hyper::body::HttpBody;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    #[derive(ErrorResponse)]
+    #[error_format("json-rpc")]
+    enum Error {
+        #[error(code = 500, msg = "Internal server error!!!")]
+        Internal,
+        #[error(code = 400, msg = "Invalid params", rpc_code = -32602)]
+        InvalidParams(#[error(details)] Vec<String>),
+    }
+
+    #[tokio::test]
+    async fn right_transferred_data_json_rpc() {
+        let mut response = Error::Internal.into_response();
+        let error_msg = json!({
+            "jsonrpc": "2.0",
+            "error": { "code": -32603, "message": "Internal server error!!!" },
+            "id": null,
+        });
+
+        assert_eq!(
+            response.headers().get("Content-Type").unwrap(),
+            "application/json"
+        );
+        assert_eq!(
+            response.data().await.unwrap().unwrap().to_vec(),
+            error_msg.to_string().as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn rpc_code_and_data_json_rpc() {
+        let error_msg = json!({
+            "jsonrpc": "2.0",
+            "error": {
+                "code": -32602,
+                "message": "Invalid params",
+                "data": ["username"],
+            },
+            "id": null,
+        });
+
+        assert_eq!(
+            Error::InvalidParams(vec!["username".into()])
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            error_msg.to_string().as_bytes()
+        );
+    }
+
+    async fn handler(Json(request): Json<Value>) -> Response {
+        axum_error_macro::with_rpc_id(request["id"].clone(), async move {
+            Err::<Json<Value>, _>(Error::InvalidParams(vec![]))
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn request_id_json_rpc() {
+        let app = Router::new().route("/", post(handler));
+        let request = Request::post("/")
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({ "jsonrpc": "2.0", "method": "users", "id": 7 }).to_string(),
+            ))
+            .unwrap();
+
+        let body = app
+            .oneshot(request)
+            .await
+            .unwrap()
+            .data()
+            .await
+            .unwrap()
+            .unwrap()
+            .to_vec();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body["id"], 7);
+        assert_eq!(body["error"]["code"], -32602);
+    }
+}
@@ -1,5 +1,34 @@
+// Test enums and structs declare variants and fields that only some tests use.
+#![allow(clippy::module_inception, dead_code)]
+
+// tests/config.rs runs only as its own test binary: it sets the process-global `OnceLock`
+// behind `axum_error_macro::configure`, which would leak into every test below.
+
+mod backtrace;
+mod binary;
+mod connect;
+mod counter;
+mod details;
+mod extension;
+mod format;
+mod formatter;
+mod graphql;
+mod grpc;
+mod headers;
+mod html;
+mod internal;
+mod json_api;
+mod json_rpc;
+mod negotiate;
+mod oauth;
+mod otel;
+mod params;
+mod problem;
+mod request_id;
+mod sensitive;
 mod status_code;
+mod trace;
 mod transferred_data;
-mod params;
+mod xml;
 
-//TODO: more tests
\ No newline at end of file
+//TODO: more tests
@@ -0,0 +1,147 @@
+mod negotiate {
+    use axum::body::Body;
+    use axum::http::Request;
+    use axum::response::Response;
+    use axum::{middleware, routing::get, Router};
+    use axum_error_macro::ErrorResponse;
+    use hyper::body::HttpBody;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    #[derive(ErrorResponse)]
+    #[error_format("application/json")]
+    enum Error {
+        #[error(code = 404, msg = "User by {} id was not found")]
+        UserByIdNotFound(u32),
+        #[error(code = 503, msg = "Unhealthy", format = "text/plain")]
+        Unhealthy,
+    }
+
+    async fn handler() -> Response {
+        Error::UserByIdNotFound(12).into_response()
+    }
+
+    async fn health() -> Response {
+        Error::Unhealthy.into_response()
+    }
+
+    async fn request(accept: Option<&str>) -> Response {
+        request_to("/", accept).await
+    }
+
+    async fn request_to(uri: &str, accept: Option<&str>) -> Response {
+        let app = Router::new()
+            .route("/", get(handler))
+            .route("/health", get(health))
+            .layer(middleware::from_fn(axum_error_macro::negotiate));
+        let mut request = Request::builder().uri(uri);
+        if let Some(accept) = accept {
+            request = request.header("Accept", accept);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    fn content_type(response: &Response) -> &str {
+        response
+            .headers()
+            .get("Content-Type")
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn accepted_text() {
+        let mut response = request(Some("text/plain")).await;
+
+        assert_eq!(content_type(&response), "text/plain; charset=utf-8");
+        assert_eq!(
+            response.data().await.unwrap().unwrap().to_vec(),
+            "User by 12 id was not found".as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn accepted_html_by_quality() {
+        let response = request(Some("application/json;q=0.5, text/html, */*;q=0.1")).await;
+
+        assert_eq!(content_type(&response), "text/html; charset=utf-8");
+    }
+
+    #[tokio::test]
+    async fn accepted_xml_by_range() {
+        let response = request(Some("application/*;q=0.2, application/xml;q=0.9")).await;
+
+        assert_eq!(content_type(&response), "application/xml");
+    }
+
+    #[tokio::test]
+    async fn accepted_problem() {
+        let mut response = request(Some("application/problem+json")).await;
+
+        assert_eq!(content_type(&response), "application/problem+json");
+        assert_eq!(
+            response.data().await.unwrap().unwrap().to_vec(),
+            json!({
+                "type": "about:blank",
+                "title": "Not Found",
+                "status": 404,
+                "detail": "User by 12 id was not found",
+            })
+            .to_string()
+            .as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn accepted_problem_by_quality() {
+        let response = request(Some("application/json;q=0.8, application/problem+json")).await;
+
+        assert_eq!(content_type(&response), "application/problem+json");
+    }
+
+    #[tokio::test]
+    async fn fallback_on_any() {
+        let mut response = request(Some("*/*")).await;
+
+        assert_eq!(content_type(&response), "application/json");
+        assert_eq!(
+            response.data().await.unwrap().unwrap().to_vec(),
+            json!({ "message": "User by 12 id was not found" })
+                .to_string()
+                .as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn fallback_on_missing_accept() {
+        let response = request(None).await;
+
+        assert_eq!(content_type(&response), "application/json");
+    }
+
+    #[tokio::test]
+    async fn fallback_on_not_acceptable() {
+        let response = request(Some("image/png, application/json;q=0")).await;
+
+        assert_eq!(response.status(), 404);
+        assert_eq!(content_type(&response), "application/json");
+    }
+
+    #[test]
+    fn fallback_without_middleware() {
+        assert_eq!(
+            content_type(&Error::UserByIdNotFound(12).into_response()),
+            "application/json"
+        );
+    }
+
+    #[tokio::test]
+    async fn pinned_variant_format() {
+        let response = request_to("/health", Some("application/json")).await;
+
+        assert_eq!(content_type(&response), "text/plain; charset=utf-8");
+    }
+}
@@ -0,0 +1,62 @@
+mod oauth {
+    use axum_error_macro::ErrorResponse;
+    use hyper::body::HttpBody;
+    use serde_json::json;
+
+    #[derive(ErrorResponse)]
+    #[error_format("oauth2")]
+    enum Error {
+        #[error(
+            code = 400,
+            msg = "Grant {} has expired",
+            oauth_error = "invalid_grant"
+        )]
+        ExpiredGrant(String),
+        #[error(
+            code = 401,
+            msg = "The access token is \"expired\"",
+            oauth_error = "invalid_token",
+            error_uri = "https://example.com/docs/tokens"
+        )]
+        ExpiredToken,
+    }
+
+    #[tokio::test]
+    async fn right_transferred_data_oauth() {
+        let mut response = Error::ExpiredGrant("abc".into()).into_response();
+        let error_msg = json!({
+            "error": "invalid_grant",
+            "error_description": "Grant abc has expired",
+        });
+
+        assert_eq!(response.status(), 400);
+        assert_eq!(
+            response.headers().get("Content-Type").unwrap(),
+            "application/json"
+        );
+        assert!(response.headers().get("WWW-Authenticate").is_none());
+        assert_eq!(
+            response.data().await.unwrap().unwrap().to_vec(),
+            error_msg.to_string().as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn unauthorized_oauth() {
+        let mut response = Error::ExpiredToken.into_response();
+        let error_msg = json!({
+            "error": "invalid_token",
+            "error_description": "The access token is \"expired\"",
+            "error_uri": "https://example.com/docs/tokens",
+        });
+
+        assert_eq!(
+            response.headers().get("WWW-Authenticate").unwrap(),
+            "Bearer error=\"invalid_token\", error_description=\"The access token is expired\", error_uri=\"https://example.com/docs/tokens\""
+        );
+        assert_eq!(
+            response.data().await.unwrap().unwrap().to_vec(),
+            error_msg.to_string().as_bytes()
+        );
+    }
+}
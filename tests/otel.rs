@@ -0,0 +1,104 @@
+#![cfg(feature = "opentelemetry")]
+
+mod otel {
+    use std::borrow::Cow;
+    use std::sync::{Arc, Mutex};
+    use std::time::SystemTime;
+
+    use axum_error_macro::ErrorResponse;
+    use opentelemetry::trace::{Span, SpanContext, Status, TraceContextExt};
+    use opentelemetry::{Context, KeyValue};
+
+    #[derive(Default)]
+    struct Recorded {
+        status: Option<Status>,
+        events: Vec<(String, Vec<KeyValue>)>,
+    }
+
+    struct RecordingSpan(SpanContext, Arc<Mutex<Recorded>>);
+
+    impl Span for RecordingSpan {
+        fn add_event_with_timestamp<T>(&mut self, name: T, _: SystemTime, attributes: Vec<KeyValue>)
+        where
+            T: Into<Cow<'static, str>>,
+        {
+            let event = (name.into().into_owned(), attributes);
+            self.1.lock().unwrap().events.push(event);
+        }
+
+        fn span_context(&self) -> &SpanContext {
+            &self.0
+        }
+
+        fn is_recording(&self) -> bool {
+            true
+        }
+
+        fn set_attribute(&mut self, _: KeyValue) {}
+
+        fn set_status(&mut self, status: Status) {
+            self.1.lock().unwrap().status = Some(status);
+        }
+
+        fn update_name<T>(&mut self, _: T)
+        where
+            T: Into<Cow<'static, str>>,
+        {
+        }
+
+        fn add_link(&mut self, _: SpanContext, _: Vec<KeyValue>) {}
+
+        fn end_with_timestamp(&mut self, _: SystemTime) {}
+    }
+
+    fn recorded(f: impl FnOnce()) -> Recorded {
+        let recorded = Arc::new(Mutex::new(Recorded::default()));
+        let span = RecordingSpan(SpanContext::empty_context(), recorded.clone());
+        let guard = Context::current_with_span(span).attach();
+        f();
+        drop(guard);
+        Arc::try_unwrap(recorded)
+            .ok()
+            .unwrap()
+            .into_inner()
+            .unwrap()
+    }
+
+    #[derive(ErrorResponse)]
+    enum Error {
+        #[error(code = 500, msg = "Internal error")]
+        Internal,
+
+        #[error(code = 404, msg = "User by {} id was not found")]
+        UserByIdNotFound(u32),
+    }
+
+    #[test]
+    fn server_error_span() {
+        let recorded = recorded(|| {
+            Error::Internal.into_response();
+        });
+
+        assert_eq!(recorded.status, Some(Status::error("Internal error")));
+        assert_eq!(
+            recorded.events,
+            vec![(
+                "exception".to_string(),
+                vec![
+                    KeyValue::new("exception.type", "Error::Internal"),
+                    KeyValue::new("exception.message", "Internal error"),
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn client_error_span() {
+        let recorded = recorded(|| {
+            Error::UserByIdNotFound(12).into_response();
+        });
+
+        assert_eq!(recorded.status, None);
+        assert!(recorded.events.is_empty());
+    }
+}
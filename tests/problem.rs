@@ -0,0 +1,90 @@
+mod problem {
+    use axum_error_macro::ErrorResponse;
+    use hyper::body::HttpBody;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn right_transferred_data_problem() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/problem+json")]
+        enum Error {
+            #[error(code = 404, msg = "User by {} id was not found")]
+            UserByIdNotFound(u32),
+        }
+
+        let mut response = Error::UserByIdNotFound(12).into_response();
+        let problem = json!({
+            "type": "about:blank",
+            "title": "Not Found",
+            "status": 404,
+            "detail": "User by 12 id was not found",
+        });
+
+        assert_eq!(
+            response.headers().get("Content-Type").unwrap(),
+            "application/problem+json"
+        );
+        assert_eq!(
+            response.data().await.unwrap().unwrap().to_vec(),
+            problem.to_string().as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn title_and_details_problem() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/problem+json")]
+        enum Error {
+            #[error(code = 422, msg = "Validation failed", title = "Invalid Fields")]
+            InvalidFields(#[error(details)] Vec<&'static str>),
+        }
+
+        assert_eq!(
+            Error::InvalidFields(vec!["email"])
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            json!({
+                "type": "about:blank",
+                "title": "Invalid Fields",
+                "status": 422,
+                "detail": "Validation failed",
+                "details": ["email"],
+            })
+            .to_string()
+            .as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn error_code_problem() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/problem+json")]
+        enum Error {
+            #[error(code = 409, msg = "User already exists", error_code = "USER_EXISTS")]
+            UserExists,
+        }
+
+        assert_eq!(
+            Error::UserExists
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            json!({
+                "type": "about:blank",
+                "title": "Conflict",
+                "status": 409,
+                "detail": "User already exists",
+                "code": "USER_EXISTS",
+            })
+            .to_string()
+            .as_bytes()
+        );
+    }
+}
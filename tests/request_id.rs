@@ -0,0 +1,104 @@
+mod request_id {
+    use axum::body::Body;
+    use axum::http::Request;
+    use axum::response::Response;
+    use axum::{middleware, routing::get, Router};
+    use axum_error_macro::ErrorResponse;
+    use hyper::body::HttpBody;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    #[derive(ErrorResponse)]
+    #[error_format("application/json")]
+    #[error(request_id = "X-Request-Id")]
+    enum Error {
+        #[error(code = 404, msg = "User by {} id was not found")]
+        UserByIdNotFound(u32),
+    }
+
+    async fn handler() -> Response {
+        Error::UserByIdNotFound(12).into_response()
+    }
+
+    async fn request(headers: &[(&str, &str)]) -> Response {
+        let app = Router::new()
+            .route("/", get(handler))
+            .layer(middleware::from_fn(axum_error_macro::request_id));
+        let mut request = Request::builder().uri("/");
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn x_request_id() {
+        let mut response = request(&[("X-Request-Id", "f81d4fae")]).await;
+
+        assert_eq!(response.headers()["X-Request-Id"], "f81d4fae");
+        assert_eq!(
+            response.data().await.unwrap().unwrap().to_vec(),
+            json!({ "message": "User by 12 id was not found", "request_id": "f81d4fae" })
+                .to_string()
+                .as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn traceparent() {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let response = request(&[("traceparent", traceparent)]).await;
+
+        assert_eq!(
+            response.headers()["X-Request-Id"],
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_x_request_id() {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let long = "a".repeat(129);
+        for id in ["f81d 4fae", "f81d\t4fae", long.as_str()] {
+            let response = request(&[("X-Request-Id", id), ("traceparent", traceparent)]).await;
+
+            assert_eq!(
+                response.headers()["X-Request-Id"],
+                "4bf92f3577b34da6a3ce929d0e0e4736"
+            );
+        }
+
+        let response = request(&[("X-Request-Id", "caf\u{e9}")]).await;
+        assert!(response.headers().get("X-Request-Id").is_none());
+    }
+
+    #[tokio::test]
+    async fn longest_x_request_id() {
+        let id = "a".repeat(128);
+        let response = request(&[("X-Request-Id", &id)]).await;
+
+        assert_eq!(response.headers()["X-Request-Id"], id.as_str());
+    }
+
+    #[tokio::test]
+    async fn without_request_id() {
+        let mut response = request(&[]).await;
+
+        assert!(response.headers().get("X-Request-Id").is_none());
+        assert_eq!(
+            response.data().await.unwrap().unwrap().to_vec(),
+            json!({ "message": "User by 12 id was not found" })
+                .to_string()
+                .as_bytes()
+        );
+    }
+
+    #[test]
+    fn without_middleware() {
+        let response = Error::UserByIdNotFound(12).into_response();
+
+        assert!(response.headers().get("X-Request-Id").is_none());
+    }
+}
@@ -0,0 +1,80 @@
+mod sensitive {
+    use axum_error_macro::ErrorResponse;
+    use hyper::body::HttpBody;
+    use serde_json::json;
+
+    #[derive(Debug)]
+    struct User {
+        email: String,
+    }
+
+    #[derive(ErrorResponse)]
+    #[error_format("application/json")]
+    enum Error {
+        #[error(code = 404, msg = "User {:?} was not found")]
+        UserNotFound(#[sensitive] User),
+
+        #[error(
+            code = 401,
+            msg = "Token {1} of {} is invalid",
+            auth_challenge = "Bearer"
+        )]
+        InvalidToken(String, #[sensitive] String),
+    }
+
+    #[tokio::test]
+    async fn masked_message() {
+        let error = Error::UserNotFound(User {
+            email: "bebra@example.com".into(),
+        });
+        assert_eq!(
+            error
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            json!({ "message": "User *** was not found" })
+                .to_string()
+                .as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn masked_indexed_message() {
+        assert_eq!(
+            Error::InvalidToken("Bebra".into(), "secret".into())
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            json!({ "message": "Token *** of Bebra is invalid" })
+                .to_string()
+                .as_bytes()
+        );
+    }
+
+    #[test]
+    fn raw_internal_message() {
+        #[derive(ErrorResponse)]
+        enum Error {
+            #[error(
+                code = 404,
+                msg = "User {:?} was not found",
+                internal = "lookup of {0:?}"
+            )]
+            UserNotFound(#[sensitive] User),
+        }
+
+        let error = Error::UserNotFound(User {
+            email: "bebra@example.com".into(),
+        });
+        assert_eq!(
+            error.internal_message(),
+            Some("lookup of User { email: \"bebra@example.com\" }".into())
+        );
+    }
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "tracing")]
+
+mod trace {
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    use axum_error_macro::ErrorResponse;
+    use tracing::field::Empty;
+
+    #[derive(Clone, Default)]
+    struct Logs(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Logs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn traced(f: impl FnOnce()) -> String {
+        let logs = Logs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_ansi(false)
+            .without_time()
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, f);
+        let logs = logs.0.lock().unwrap().clone();
+        String::from_utf8(logs).unwrap()
+    }
+
+    #[derive(ErrorResponse)]
+    enum Error {
+        #[error(code = 500, msg = "Internal error", internal = "db failure: {0}")]
+        Database(String),
+
+        #[error(
+            code = 404,
+            msg = "User by {} id was not found",
+            error_code = "USER_NOT_FOUND"
+        )]
+        UserByIdNotFound(u32),
+
+        #[error(code = 409, msg = "User already exists", log = "info")]
+        UserExists,
+    }
+
+    #[test]
+    fn level_by_status() {
+        let logs = traced(|| {
+            Error::Database("connection reset".into()).into_response();
+            Error::UserByIdNotFound(12).into_response();
+        });
+
+        let lines: Vec<&str> = logs.lines().collect();
+        assert!(lines[0].starts_with("ERROR"));
+        assert!(lines[0].contains("status=500"));
+        assert!(lines[0].contains("variant=\"Database\""));
+        assert!(lines[0].contains(" Internal error "));
+        assert!(lines[0].contains("internal=\"db failure: connection reset\""));
+        assert!(!lines[0].contains("error_code"));
+        assert!(lines[1].starts_with(" WARN"));
+        assert!(lines[1].contains("error_code=\"USER_NOT_FOUND\""));
+        assert!(lines[1].ends_with(" User by 12 id was not found"));
+    }
+
+    #[test]
+    fn variant_level() {
+        let logs = traced(|| {
+            Error::UserExists.into_response();
+        });
+
+        assert!(logs.starts_with(" INFO"));
+        assert!(logs.contains("variant=\"UserExists\""));
+    }
+
+    #[test]
+    fn span_fields() {
+        let logs = traced(|| {
+            let span = tracing::info_span!(
+                "request",
+                status = Empty,
+                variant = Empty,
+                error_code = Empty
+            );
+            let _entered = span.enter();
+            Error::UserByIdNotFound(12).into_response();
+            tracing::info!("handled");
+        });
+
+        let handled = logs.lines().last().unwrap();
+        assert!(handled.contains(
+            "request{status=404 variant=\"UserByIdNotFound\" error_code=\"USER_NOT_FOUND\"}"
+        ));
+    }
+}
@@ -154,4 +154,27 @@ mod transferred_data {
             msg.to_string().as_bytes()
         );
     }
+
+    #[tokio::test]
+    async fn error_code_json() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/json")]
+        enum Error {
+            #[error(code = 409, msg = "User already exists", error_code = "USER_EXISTS")]
+            UserExists,
+        }
+
+        assert_eq!(
+            Error::UserExists
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            json!({ "message": "User already exists", "code": "USER_EXISTS" })
+                .to_string()
+                .as_bytes()
+        );
+    }
 }
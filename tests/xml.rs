@@ -0,0 +1,128 @@
+mod xml {
+    use std::collections::BTreeMap;
+
+    use axum_error_macro::ErrorResponse;
+    use hyper::body::HttpBody;
+    use serde::Serialize;
+
+    #[tokio::test]
+    async fn right_transferred_data_xml() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/xml")]
+        enum Error {
+            #[error(code = 404, msg = "User by {} username was not found")]
+            UserByUsernameNotFound(String),
+        }
+
+        let response = Error::UserByUsernameNotFound("<Bebra & co>".into()).into_response();
+
+        assert_eq!(
+            response.headers().get("Content-Type").unwrap(),
+            "application/xml"
+        );
+        assert_eq!(
+            response.into_body().data().await.unwrap().unwrap().to_vec(),
+            "<error><status>404</status><message>User by &lt;Bebra &amp; co&gt; username was not found</message></error>"
+                .as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn root_element_xml() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/xml", root = "fault")]
+        enum Error {
+            #[error(code = 500, msg = "Internal server error!!!")]
+            InternalServerError,
+        }
+
+        assert_eq!(
+            Error::InternalServerError
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            "<fault><status>500</status><message>Internal server error!!!</message></fault>"
+                .as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn details_xml() {
+        #[derive(Serialize)]
+        struct FieldError {
+            field: String,
+            reason: String,
+        }
+
+        #[derive(ErrorResponse)]
+        #[error_format("application/xml")]
+        enum Error {
+            #[error(code = 422, msg = "Validation failed")]
+            InvalidFields(#[error(details)] Vec<FieldError>),
+        }
+        let fields = vec![FieldError {
+            field: "email".into(),
+            reason: "must be < 255 chars".into(),
+        }];
+
+        assert_eq!(
+            Error::InvalidFields(fields)
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            "<error><status>422</status><message>Validation failed</message><details><item><field>email</field><reason>must be &lt; 255 chars</reason></item></details></error>"
+                .as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_key_xml() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/xml")]
+        enum Error {
+            #[error(code = 422, msg = "Validation failed")]
+            InvalidFields(#[error(details)] BTreeMap<String, String>),
+        }
+        let fields = BTreeMap::from([(String::new(), "must not be empty".to_string())]);
+
+        assert_eq!(
+            Error::InvalidFields(fields)
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            "<error><status>422</status><message>Validation failed</message><details><_>must not be empty</_></details></error>"
+                .as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn error_code_xml() {
+        #[derive(ErrorResponse)]
+        #[error_format("application/xml")]
+        enum Error {
+            #[error(code = 404, msg = "User was not found", error_code = "USER_NOT_FOUND")]
+            UserNotFound,
+        }
+
+        assert_eq!(
+            Error::UserNotFound
+                .into_response()
+                .data()
+                .await
+                .unwrap()
+                .unwrap()
+                .to_vec(),
+            "<error><status>404</status><code>USER_NOT_FOUND</code><message>User was not found</message></error>"
+                .as_bytes()
+        );
+    }
+}